@@ -127,7 +127,7 @@ impl RustChallengeInteractor {
             .to(self.state.current_rust_challenge_address())
             .gas(8_000_000)
             .typed(rust_challenge_proxy::RustChallengeProxy)
-            .deposit(receiver)
+            .deposit(receiver, OptionalValue::<Bech32Address>::None)
             .egld(value)
             .prepare_async()
             .run()
@@ -206,7 +206,7 @@ async fn deposit_with_errors() {
         .to(interact.state.current_rust_challenge_address())
         .gas(8_000_000)
         .typed(rust_challenge_proxy::RustChallengeProxy)
-        .deposit(receiver)
+        .deposit(receiver, OptionalValue::<Bech32Address>::None)
         .prepare_async()
         .run()
         .await;
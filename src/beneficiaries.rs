@@ -0,0 +1,91 @@
+use multiversx_sc::imports::*;
+
+use crate::{compliance, roles};
+
+pub const MAX_BPS: u32 = 10_000;
+pub const MAX_BENEFICIARIES: usize = 10;
+
+/// Splits deposit fees between beneficiaries by basis points, each claiming their own part.
+#[multiversx_sc::module]
+pub trait BeneficiariesModule: roles::RolesModule + compliance::ComplianceModule {
+    /// Replaces the beneficiary table. Shares must add up to 10000 basis points, or the
+    /// table must be empty, in which case all fees go to the owner again.
+    #[only_owner]
+    #[endpoint(setBeneficiaries)]
+    fn set_beneficiaries(
+        &self,
+        beneficiaries: MultiValueEncoded<MultiValue2<ManagedAddress, u32>>,
+    ) {
+        require!(
+            beneficiaries.len() <= MAX_BENEFICIARIES,
+            "Too many beneficiaries"
+        );
+
+        let mut shares = self.beneficiary_shares();
+        shares.clear();
+        let mut total_shares = 0u32;
+        for beneficiary in beneficiaries {
+            let (address, share) = beneficiary.into_tuple();
+            require!(share > 0, "Share must be greater than zero");
+            require!(
+                shares.insert(address, share).is_none(),
+                "Duplicate beneficiary"
+            );
+            total_shares += share;
+        }
+        require!(
+            shares.is_empty() || total_shares == MAX_BPS,
+            "Shares must add up to 10000 basis points"
+        );
+    }
+
+    /// Accrues the beneficiaries' parts of `fee` and returns what is left for the owner.
+    fn distribute_fee(&self, fee: &BigUint) -> BigUint {
+        let mut remainder = fee.clone();
+        for (address, share) in self.beneficiary_shares().iter() {
+            let part = fee * share / MAX_BPS;
+            if part == 0 {
+                continue;
+            }
+            self.accrued_fees(&address)
+                .update(|accrued| *accrued += &part);
+            self.total_accrued_fees().update(|total| *total += &part);
+            remainder -= part;
+        }
+        remainder
+    }
+
+    #[endpoint(claimFees)]
+    fn claim_fees(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.is_address_permitted(&caller),
+            "Caller is not permitted"
+        );
+        let amount = self.accrued_fees(&caller).take();
+        require!(amount > 0, "No fees to claim");
+
+        self.total_accrued_fees().update(|total| *total -= &amount);
+        self.tx().to(&caller).egld(amount).transfer();
+    }
+
+    #[view(getBeneficiaries)]
+    fn get_beneficiaries(&self) -> MultiValueEncoded<MultiValue2<ManagedAddress, u32>> {
+        let mut result = MultiValueEncoded::new();
+        for (address, share) in self.beneficiary_shares().iter() {
+            result.push((address, share).into());
+        }
+        result
+    }
+
+    #[storage_mapper("beneficiaryShares")]
+    fn beneficiary_shares(&self) -> MapMapper<ManagedAddress, u32>;
+
+    #[view(getAccruedFees)]
+    #[storage_mapper("accruedFees")]
+    fn accrued_fees(&self, beneficiary: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalAccruedFees)]
+    #[storage_mapper("totalAccruedFees")]
+    fn total_accrued_fees(&self) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,95 @@
+use multiversx_sc::imports::*;
+
+use crate::events;
+use crate::rate_limit::WithdrawalWindow;
+use crate::roles::{self, Role};
+
+/// Pauses all withdrawals once total outflow within a window would exceed a threshold.
+/// Only a guardian can lift the pause.
+#[multiversx_sc::module]
+pub trait CircuitBreakerModule: roles::RolesModule + events::EventsModule {
+    /// A zero threshold disables the breaker.
+    #[only_owner]
+    #[endpoint(setCircuitBreaker)]
+    fn set_circuit_breaker(&self, threshold: BigUint, window: u64) {
+        require!(
+            threshold == 0 || window > 0,
+            "Window must be greater than zero"
+        );
+        self.outflow_threshold().set(threshold);
+        self.outflow_window_length().set(window);
+    }
+
+    #[endpoint(resetCircuitBreaker)]
+    fn reset_circuit_breaker(&self) {
+        self.require_role(Role::Guardian);
+        self.withdrawals_paused().clear();
+        self.outflow_window().clear();
+    }
+
+    fn require_withdrawals_not_paused(&self) {
+        require!(!self.withdrawals_paused().get(), "Withdrawals are paused");
+    }
+
+    /// Adds `amount` to the outflow of the current window. If that would exceed the
+    /// threshold, trips the breaker instead and returns `false`; the caller must then skip
+    /// the payout without failing, so that the pause is persisted.
+    fn record_outflow(&self, amount: &BigUint) -> bool {
+        let threshold = self.outflow_threshold().get();
+        if threshold == 0 {
+            return true;
+        }
+
+        let mut window = self.get_current_outflow_window();
+        window.withdrawn += amount;
+        if window.withdrawn > threshold {
+            self.withdrawals_paused().set(true);
+            self.circuit_breaker_tripped_event(&window.withdrawn, &threshold);
+            return false;
+        }
+
+        self.outflow_window().set(window);
+        true
+    }
+
+    /// Gives back an amount recorded by `record_outflow` that never left the contract.
+    fn release_outflow(&self, amount: &BigUint) {
+        let mut window = self.get_current_outflow_window();
+        if window.withdrawn == 0 {
+            return;
+        }
+        window.withdrawn -= core::cmp::min(amount, &window.withdrawn).clone();
+        self.outflow_window().set(window);
+    }
+
+    fn get_current_outflow_window(&self) -> WithdrawalWindow<Self::Api> {
+        let now = self.blockchain().get_block_timestamp();
+        if !self.outflow_window().is_empty() {
+            let window = self.outflow_window().get();
+            if now < window.start + self.outflow_window_length().get() {
+                return window;
+            }
+        }
+
+        WithdrawalWindow {
+            start: now,
+            withdrawn: BigUint::zero(),
+        }
+    }
+
+    #[view(getOutflowThreshold)]
+    #[storage_mapper("outflowThreshold")]
+    fn outflow_threshold(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getOutflowWindowLength)]
+    #[storage_mapper("outflowWindowLength")]
+    fn outflow_window_length(&self) -> SingleValueMapper<u64>;
+
+    #[view(getOutflowWindow)]
+    #[storage_mapper("outflowWindow")]
+    fn outflow_window(&self) -> SingleValueMapper<WithdrawalWindow<Self::Api>>;
+
+    #[view(areWithdrawalsPaused)]
+    #[storage_mapper("withdrawalsPaused")]
+    fn withdrawals_paused(&self) -> SingleValueMapper<bool>;
+}
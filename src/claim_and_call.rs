@@ -0,0 +1,79 @@
+use multiversx_sc::imports::*;
+
+use crate::{circuit_breaker, compliance, events, rate_limit, reserves, roles, statistics};
+
+/// Withdraws a reserve straight into an endpoint of another smart contract.
+#[multiversx_sc::module]
+pub trait ClaimAndCallModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + events::EventsModule
+    + rate_limit::RateLimitModule
+    + circuit_breaker::CircuitBreakerModule
+    + statistics::StatisticsModule
+{
+    /// Sends the caller's reserve, up to the withdrawal allowance, to `function` on `target`.
+    /// If the call fails, the EGLD comes back and is credited to the caller's reserve again,
+    /// and no longer counts against the withdrawal allowance or the circuit breaker.
+    #[endpoint(withdrawAndCall)]
+    fn withdraw_and_call(
+        &self,
+        target: ManagedAddress,
+        function: ManagedBuffer,
+        args: MultiValueEncoded<ManagedBuffer>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.is_address_permitted(&caller),
+            "Caller is not permitted"
+        );
+        self.require_withdrawals_not_paused();
+        require!(
+            self.blockchain().is_smart_contract(&target),
+            "Target must be a smart contract"
+        );
+        require!(!function.is_empty(), "Function name is required");
+
+        self.touch_activity(&caller);
+        let reserve = self.get_reserve_for_address(&caller);
+        require!(reserve > 0, "Nothing to claim");
+        let amount = self.get_allowed_withdrawal(&caller, &reserve);
+        require!(amount > 0, "Withdrawal limit reached");
+        if !self.record_outflow(&amount) {
+            return;
+        }
+        self.record_withdrawal(&caller, &amount);
+        self.debit_reserve(&caller, &amount);
+
+        self.tx()
+            .to(&target)
+            .raw_call(function)
+            .arguments_raw(args.to_arg_buffer())
+            .egld(&amount)
+            .callback(
+                self.callbacks()
+                    .withdraw_and_call_callback(&caller, &amount),
+            )
+            .async_call_and_exit();
+    }
+
+    #[callback]
+    fn withdraw_and_call_callback(
+        &self,
+        receiver: &ManagedAddress,
+        amount: &BigUint,
+        #[call_result] result: ManagedAsyncCallResult<IgnoreValue>,
+    ) {
+        match result {
+            // only a call that went through counts as a withdrawal
+            ManagedAsyncCallResult::Ok(_) => self.record_withdrawal_stats(receiver, amount),
+            ManagedAsyncCallResult::Err(_) => {
+                self.credit_reserve(receiver, amount);
+                self.release_withdrawal(receiver, amount);
+                self.release_outflow(amount);
+                self.withdraw_and_call_failed_event(receiver, amount);
+            }
+        }
+    }
+}
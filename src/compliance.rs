@@ -0,0 +1,65 @@
+use multiversx_sc::imports::*;
+
+use crate::roles::{self, Role};
+
+/// Blocklist and optional allowlist-only mode, checked for every party that moves funds.
+#[multiversx_sc::module]
+pub trait ComplianceModule: roles::RolesModule {
+    #[endpoint(addToBlocklist)]
+    fn add_to_blocklist(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_owner_or_role(Role::Compliance);
+        for address in addresses {
+            self.blocklist().insert(address);
+        }
+    }
+
+    #[endpoint(removeFromBlocklist)]
+    fn remove_from_blocklist(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_owner_or_role(Role::Compliance);
+        for address in addresses {
+            self.blocklist().swap_remove(&address);
+        }
+    }
+
+    #[endpoint(addToAllowlist)]
+    fn add_to_allowlist(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_owner_or_role(Role::Compliance);
+        for address in addresses {
+            self.allowlist().insert(address);
+        }
+    }
+
+    #[endpoint(removeFromAllowlist)]
+    fn remove_from_allowlist(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        self.require_owner_or_role(Role::Compliance);
+        for address in addresses {
+            self.allowlist().swap_remove(&address);
+        }
+    }
+
+    #[endpoint(setAllowlistOnly)]
+    fn set_allowlist_only(&self, enabled: bool) {
+        self.require_owner_or_role(Role::Compliance);
+        self.allowlist_only().set(enabled);
+    }
+
+    #[view(isAddressPermitted)]
+    fn is_address_permitted(&self, address: &ManagedAddress) -> bool {
+        if self.blocklist().contains(address) {
+            return false;
+        }
+        !self.allowlist_only().get() || self.allowlist().contains(address)
+    }
+
+    #[view(getBlocklist)]
+    #[storage_mapper("blocklist")]
+    fn blocklist(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getAllowlist)]
+    #[storage_mapper("allowlist")]
+    fn allowlist(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(isAllowlistOnly)]
+    #[storage_mapper("allowlistOnly")]
+    fn allowlist_only(&self) -> SingleValueMapper<bool>;
+}
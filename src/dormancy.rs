@@ -0,0 +1,75 @@
+use multiversx_sc::imports::*;
+
+use crate::roles::{self, Role};
+use crate::{compliance, events, reserves, statistics};
+
+/// Sweeps reserves whose receivers have been inactive for too long to a treasury.
+#[multiversx_sc::module]
+pub trait DormancyModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + events::EventsModule
+    + statistics::StatisticsModule
+{
+    /// A zero period disables sweeping.
+    #[only_owner]
+    #[endpoint(setDormancyConfig)]
+    fn set_dormancy_config(&self, period: u64, treasury: ManagedAddress) {
+        self.dormancy_period().set(period);
+        self.dormancy_treasury().set(treasury);
+    }
+
+    #[endpoint(sweepDormantReserve)]
+    fn sweep_dormant_reserve(&self, receiver: ManagedAddress) {
+        self.require_owner_or_role(Role::Admin);
+        require!(
+            self.dormancy_period().get() > 0,
+            "Dormancy sweeping is disabled"
+        );
+        // frozen reserves stay in place
+        require!(
+            self.is_address_permitted(&receiver),
+            "Receiver is not permitted"
+        );
+        // a reserve missed by the migration has no recorded activity, so it is not dormant yet
+        self.index_reserve(&receiver);
+        require!(
+            self.blockchain().get_block_timestamp() >= self.get_expiry_timestamp(&receiver),
+            "Reserve is not dormant"
+        );
+
+        let amount = self.get_reserve_for_address(&receiver);
+        require!(amount > 0, "Nothing to sweep");
+        self.debit_reserve(&receiver, &amount);
+        self.record_withdrawal_stats(&receiver, &amount);
+
+        let treasury = self.dormancy_treasury().get();
+        self.tx().to(&treasury).egld(&amount).transfer();
+        self.dormant_reserve_swept_event(&receiver, &treasury, &amount);
+    }
+
+    fn get_expiry_timestamp(&self, receiver: &ManagedAddress) -> u64 {
+        self.last_activity(receiver)
+            .get()
+            .saturating_add(self.dormancy_period().get())
+    }
+
+    /// Seconds left until the receiver's reserve can be swept, if sweeping applies to it.
+    #[view(getTimeToExpiry)]
+    fn get_time_to_expiry(&self, receiver: &ManagedAddress) -> OptionalValue<u64> {
+        if self.dormancy_period().get() == 0 || self.reserve_for_address(receiver).is_empty() {
+            return OptionalValue::None;
+        }
+        let now = self.blockchain().get_block_timestamp();
+        OptionalValue::Some(self.get_expiry_timestamp(receiver).saturating_sub(now))
+    }
+
+    #[view(getDormancyPeriod)]
+    #[storage_mapper("dormancyPeriod")]
+    fn dormancy_period(&self) -> SingleValueMapper<u64>;
+
+    #[view(getDormancyTreasury)]
+    #[storage_mapper("dormancyTreasury")]
+    fn dormancy_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+}
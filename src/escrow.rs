@@ -0,0 +1,236 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::{compliance, events, limits, registration, reserves, roles, statistics};
+
+pub const MAX_TRANCHES: usize = 20;
+
+#[type_abi]
+#[derive(
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    ManagedVecItem,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+)]
+pub enum TrancheStatus {
+    Locked,
+    Released,
+    Cancelled,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug,
+)]
+pub struct Tranche<M: ManagedTypeApi> {
+    pub name: ManagedBuffer<M>,
+    pub amount: BigUint<M>,
+    pub status: TrancheStatus,
+}
+
+/// A zero deadline means the sender can never cancel on their own, only the arbiter can.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Escrow<M: ManagedTypeApi> {
+    pub sender: ManagedAddress<M>,
+    pub receiver: ManagedAddress<M>,
+    pub arbiter: ManagedAddress<M>,
+    pub deadline: u64,
+    pub tranches: ManagedVec<M, Tranche<M>>,
+}
+
+/// Milestone escrows: the sender locks a payment split into named tranches, which are
+/// released one by one into the receiver's reserve. Locked tranches can be cancelled back
+/// to the sender by the arbiter at any time, or by the sender once the deadline has passed.
+#[multiversx_sc::module]
+pub trait EscrowModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + limits::LimitsModule
+    + events::EventsModule
+    + statistics::StatisticsModule
+{
+    /// The tranche amounts must add up to the payment. No deposit fee is charged.
+    #[payable("EGLD")]
+    #[endpoint(createEscrow)]
+    fn create_escrow(
+        &self,
+        receiver: ManagedAddress,
+        arbiter: ManagedAddress,
+        deadline: u64,
+        tranches: MultiValueEncoded<MultiValue2<ManagedBuffer, BigUint>>,
+    ) -> u64 {
+        let sender = self.blockchain().get_caller();
+        let payment_amount = self.call_value().egld_value().clone_value();
+        require!(
+            self.is_address_permitted(&sender),
+            "Sender is not permitted"
+        );
+        require!(
+            self.is_address_permitted(&receiver),
+            "Receiver is not permitted"
+        );
+        self.require_receiver_accepts_deposits(&receiver);
+        require!(sender != receiver, "Cannot escrow to yourself");
+        require!(!tranches.is_empty(), "No tranches");
+        require!(tranches.len() <= MAX_TRANCHES, "Too many tranches");
+
+        let mut tranche_list = ManagedVec::new();
+        let mut total = BigUint::zero();
+        for tranche in tranches.into_iter() {
+            let (name, amount) = tranche.into_tuple();
+            require!(!name.is_empty(), "Tranche name cannot be empty");
+            require!(amount > 0, "Tranche amount must be greater than zero");
+            total += &amount;
+            tranche_list.push(Tranche {
+                name,
+                amount,
+                status: TrancheStatus::Locked,
+            });
+        }
+        require!(
+            total == payment_amount,
+            "Tranches must add up to the payment"
+        );
+        // the reserve cap is checked again as each tranche is released
+        self.require_deposit_within_limits(
+            &payment_amount,
+            &self.get_reserve_for_address(&receiver),
+        );
+
+        let escrow_id = self.last_escrow_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.escrows(escrow_id).set(Escrow {
+            sender: sender.clone(),
+            receiver: receiver.clone(),
+            arbiter,
+            deadline,
+            tranches: tranche_list,
+        });
+        self.total_escrowed().update(|escrowed| *escrowed += &total);
+        self.escrow_created_event(escrow_id, &sender, &receiver, &total);
+
+        escrow_id
+    }
+
+    /// Called by the sender or the arbiter. Credits the tranche to the receiver's reserve.
+    #[endpoint(releaseTranche)]
+    fn release_tranche(&self, escrow_id: u64, tranche_index: usize) {
+        let caller = self.blockchain().get_caller();
+        let mut escrow = self.get_existing_escrow(escrow_id);
+        require!(
+            caller == escrow.sender || caller == escrow.arbiter,
+            "Only the sender or the arbiter can release"
+        );
+        require!(
+            self.is_address_permitted(&escrow.receiver),
+            "Receiver is not permitted"
+        );
+
+        let tranche = self.settle_tranche(&mut escrow, tranche_index, TrancheStatus::Released);
+        self.require_reserve_within_cap(
+            &(self.get_reserve_for_address(&escrow.receiver) + &tranche.amount),
+        );
+        self.escrows(escrow_id).set(&escrow);
+        self.total_escrowed()
+            .update(|escrowed| *escrowed -= &tranche.amount);
+
+        self.credit_reserve(&escrow.receiver, &tranche.amount);
+        self.record_received_stats(&escrow.receiver, &tranche.amount);
+        self.record_deposit(
+            &escrow.receiver,
+            &reserves::DepositRecord {
+                sender: escrow.sender.clone(),
+                amount: tranche.amount.clone(),
+                timestamp: self.blockchain().get_block_timestamp(),
+                memo: tranche.name.clone(),
+            },
+        );
+        self.tranche_released_event(escrow_id, tranche_index, &escrow.receiver, &tranche.amount);
+    }
+
+    /// Called by the arbiter, or by the sender once the deadline has passed.
+    /// Refunds the tranche to the sender.
+    #[endpoint(cancelTranche)]
+    fn cancel_tranche(&self, escrow_id: u64, tranche_index: usize) {
+        let caller = self.blockchain().get_caller();
+        let mut escrow = self.get_existing_escrow(escrow_id);
+        if caller != escrow.arbiter {
+            require!(
+                caller == escrow.sender,
+                "Only the sender or the arbiter can cancel"
+            );
+            require!(
+                escrow.deadline != 0 && self.blockchain().get_block_timestamp() > escrow.deadline,
+                "Escrow deadline has not passed"
+            );
+        }
+        require!(
+            self.is_address_permitted(&escrow.sender),
+            "Sender is not permitted"
+        );
+
+        let tranche = self.settle_tranche(&mut escrow, tranche_index, TrancheStatus::Cancelled);
+        self.escrows(escrow_id).set(&escrow);
+        self.total_escrowed()
+            .update(|escrowed| *escrowed -= &tranche.amount);
+
+        self.tx()
+            .to(&escrow.sender)
+            .egld(&tranche.amount)
+            .transfer();
+        self.tranche_cancelled_event(escrow_id, tranche_index, &escrow.sender, &tranche.amount);
+    }
+
+    fn get_existing_escrow(&self, escrow_id: u64) -> Escrow<Self::Api> {
+        require!(!self.escrows(escrow_id).is_empty(), "Escrow not found");
+        self.escrows(escrow_id).get()
+    }
+
+    fn settle_tranche(
+        &self,
+        escrow: &mut Escrow<Self::Api>,
+        tranche_index: usize,
+        status: TrancheStatus,
+    ) -> Tranche<Self::Api> {
+        require!(tranche_index < escrow.tranches.len(), "Tranche not found");
+        let mut tranche = escrow.tranches.get(tranche_index);
+        require!(
+            tranche.status == TrancheStatus::Locked,
+            "Tranche already settled"
+        );
+        tranche.status = status;
+        let _ = escrow.tranches.set(tranche_index, &tranche);
+        tranche
+    }
+
+    #[view(getEscrow)]
+    fn get_escrow(&self, escrow_id: u64) -> OptionalValue<Escrow<Self::Api>> {
+        if self.escrows(escrow_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.escrows(escrow_id).get())
+    }
+
+    #[storage_mapper("escrows")]
+    fn escrows(&self, escrow_id: u64) -> SingleValueMapper<Escrow<Self::Api>>;
+
+    #[view(getLastEscrowId)]
+    #[storage_mapper("lastEscrowId")]
+    fn last_escrow_id(&self) -> SingleValueMapper<u64>;
+
+    /// Sum of all tranches that are still locked.
+    #[view(getTotalEscrowed)]
+    #[storage_mapper("totalEscrowed")]
+    fn total_escrowed(&self) -> SingleValueMapper<BigUint>;
+}
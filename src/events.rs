@@ -0,0 +1,150 @@
+#[multiversx_sc::module]
+pub trait EventsModule {
+    #[event("deposit")]
+    fn deposit_event(
+        &self,
+        #[indexed] sender: &ManagedAddress,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        memo: &ManagedBuffer,
+    );
+
+    #[event("withdrawAndCallFailed")]
+    fn withdraw_and_call_failed_event(
+        &self,
+        #[indexed] receiver: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("dormantReserveSwept")]
+    fn dormant_reserve_swept_event(
+        &self,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] treasury: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("circuitBreakerTripped")]
+    fn circuit_breaker_tripped_event(&self, #[indexed] outflow: &BigUint, threshold: &BigUint);
+
+    #[event("reserveRecovered")]
+    fn reserve_recovered_event(
+        &self,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] recovery_address: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("operatorWithdrawal")]
+    fn operator_withdrawal_event(
+        &self,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] operator: &ManagedAddress,
+        #[indexed] destination: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("signedWithdrawal")]
+    fn signed_withdrawal_event(
+        &self,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] relayer: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        relayer_tip: &BigUint,
+    );
+
+    #[event("escrowCreated")]
+    fn escrow_created_event(
+        &self,
+        #[indexed] escrow_id: u64,
+        #[indexed] sender: &ManagedAddress,
+        #[indexed] receiver: &ManagedAddress,
+        total: &BigUint,
+    );
+
+    #[event("trancheReleased")]
+    fn tranche_released_event(
+        &self,
+        #[indexed] escrow_id: u64,
+        #[indexed] tranche_index: usize,
+        #[indexed] receiver: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("trancheCancelled")]
+    fn tranche_cancelled_event(
+        &self,
+        #[indexed] escrow_id: u64,
+        #[indexed] tranche_index: usize,
+        #[indexed] sender: &ManagedAddress,
+        amount: &BigUint,
+    );
+
+    #[event("subscriptionPull")]
+    fn subscription_pull_event(
+        &self,
+        #[indexed] subscription_id: u64,
+        #[indexed] subscriber: &ManagedAddress,
+        #[indexed] merchant: &ManagedAddress,
+        #[indexed] period: u64,
+        amount: &BigUint,
+    );
+
+    #[event("subscriptionCancelled")]
+    fn subscription_cancelled_event(
+        &self,
+        #[indexed] subscription_id: u64,
+        #[indexed] subscriber: &ManagedAddress,
+        #[indexed] merchant: &ManagedAddress,
+        refund: &BigUint,
+    );
+
+    #[event("reserveTransfer")]
+    fn reserve_transfer_event(
+        &self,
+        #[indexed] from: &ManagedAddress,
+        #[indexed] to: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        fee: &BigUint,
+    );
+
+    #[event("feeProposal")]
+    fn fee_proposal_event(
+        &self,
+        #[indexed] proposal_id: u64,
+        #[indexed] proposer: &ManagedAddress,
+        #[indexed] new_fee: &BigUint,
+        end: u64,
+    );
+
+    #[event("feeVote")]
+    fn fee_vote_event(
+        &self,
+        #[indexed] proposal_id: u64,
+        #[indexed] voter: &ManagedAddress,
+        #[indexed] support: bool,
+        weight: &BigUint,
+    );
+
+    #[event("feeProposalExecuted")]
+    fn fee_proposal_executed_event(&self, #[indexed] proposal_id: u64, new_fee: &BigUint);
+
+    #[event("feesForwarded")]
+    fn fees_forwarded_event(&self, #[indexed] treasury: &ManagedAddress, amount: &BigUint);
+
+    #[event("receiptMinted")]
+    fn receipt_minted_event(
+        &self,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] nonce: u64,
+        amount: &BigUint,
+    );
+
+    #[event("receiptRedeemed")]
+    fn receipt_redeemed_event(
+        &self,
+        #[indexed] holder: &ManagedAddress,
+        #[indexed] nonce: u64,
+        amount: &BigUint,
+    );
+}
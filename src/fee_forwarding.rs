@@ -0,0 +1,56 @@
+use multiversx_sc::imports::*;
+
+use crate::events;
+
+/// Forwards the owner's collected fees to a treasury once they reach a threshold.
+#[multiversx_sc::module]
+pub trait FeeForwardingModule: events::EventsModule {
+    /// A zero threshold disables forwarding. Treasury contracts must be payable by
+    /// contracts, so that forwarding cannot make deposits fail.
+    #[only_owner]
+    #[endpoint(setFeeTreasury)]
+    fn set_fee_treasury(&self, treasury: ManagedAddress, threshold: BigUint) {
+        require!(
+            !treasury.is_zero() && treasury != self.blockchain().get_sc_address(),
+            "Invalid treasury address"
+        );
+        require!(
+            self.is_payable_treasury(&treasury),
+            "Treasury contract must be payable by contracts"
+        );
+        self.fee_treasury().set(treasury);
+        self.fee_forward_threshold().set(threshold);
+    }
+
+    /// The treasury is checked again here, as a contract can be upgraded to non-payable after
+    /// it was set. Fees that are not forwarded stay collected for the owner.
+    fn is_fee_forwarding_due(&self, collected_fees: &BigUint) -> bool {
+        let threshold = self.fee_forward_threshold().get();
+        threshold > 0
+            && *collected_fees >= threshold
+            && self.is_payable_treasury(&self.fee_treasury().get())
+    }
+
+    fn is_payable_treasury(&self, treasury: &ManagedAddress) -> bool {
+        !self.blockchain().is_smart_contract(treasury)
+            || self
+                .blockchain()
+                .get_code_metadata(treasury)
+                .is_payable_by_sc()
+    }
+
+    /// The caller must clear the collected fees before forwarding them.
+    fn forward_fees(&self, amount: &BigUint) {
+        let treasury = self.fee_treasury().get();
+        self.tx().to(&treasury).egld(amount).transfer();
+        self.fees_forwarded_event(&treasury, amount);
+    }
+
+    #[view(getFeeTreasury)]
+    #[storage_mapper("feeTreasury")]
+    fn fee_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getFeeForwardThreshold)]
+    #[storage_mapper("feeForwardThreshold")]
+    fn fee_forward_threshold(&self) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,227 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::beneficiaries::MAX_BPS;
+use crate::events;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct FeeProposal<M: ManagedTypeApi> {
+    pub proposer: ManagedAddress<M>,
+    pub new_fee: BigUint<M>,
+    pub end: u64,
+    /// Total deposited volume when the proposal was created, the base for the quorum.
+    pub total_volume: BigUint<M>,
+    /// Quorum in basis points when the proposal was created.
+    pub quorum_bps: u32,
+    pub votes_for: BigUint<M>,
+    pub votes_against: BigUint<M>,
+    pub executed: bool,
+}
+
+/// A depositor's cumulative volume as of the deposits made while `last_proposal_id`
+/// was `proposal_id`, i.e. before proposal `proposal_id + 1` was created.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct VolumeCheckpoint<M: ManagedTypeApi> {
+    pub proposal_id: u64,
+    pub volume: BigUint<M>,
+}
+
+/// Lets depositors vote on fee changes, weighted by their cumulative deposited volume at
+/// the time the proposal was created, the same basis as the proposal's quorum. A passed
+/// proposal is executed through the fee setter.
+#[multiversx_sc::module]
+pub trait GovernanceModule: events::EventsModule {
+    /// A zero voting period disables governance.
+    #[only_owner]
+    #[endpoint(setGovernanceConfig)]
+    fn set_governance_config(&self, voting_period: u64, quorum_bps: u32) {
+        require!(
+            quorum_bps <= MAX_BPS,
+            "Quorum cannot exceed 10000 basis points"
+        );
+        self.voting_period().set(voting_period);
+        self.quorum_bps().set(quorum_bps);
+    }
+
+    #[endpoint(proposeFee)]
+    fn propose_fee(&self, new_fee: BigUint) -> u64 {
+        let caller = self.blockchain().get_caller();
+        let voting_period = self.voting_period().get();
+        require!(voting_period > 0, "Governance is disabled");
+        require!(
+            self.deposited_volume(&caller).get() > 0,
+            "Only depositors can propose"
+        );
+
+        let proposal_id = self.last_proposal_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        let end = self.blockchain().get_block_timestamp() + voting_period;
+        self.proposals(proposal_id).set(FeeProposal {
+            proposer: caller.clone(),
+            new_fee: new_fee.clone(),
+            end,
+            total_volume: self.total_deposited_volume().get(),
+            quorum_bps: self.quorum_bps().get(),
+            votes_for: BigUint::zero(),
+            votes_against: BigUint::zero(),
+            executed: false,
+        });
+        self.fee_proposal_event(proposal_id, &caller, &new_fee, end);
+
+        proposal_id
+    }
+
+    #[endpoint(vote)]
+    fn vote(&self, proposal_id: u64, support: bool) {
+        let caller = self.blockchain().get_caller();
+        let mut proposal = self.get_existing_proposal(proposal_id);
+        require!(
+            self.blockchain().get_block_timestamp() < proposal.end,
+            "Voting period has ended"
+        );
+        require!(!self.has_voted(proposal_id, &caller).get(), "Already voted");
+        let weight = self.get_voting_weight(proposal_id, &caller);
+        require!(weight > 0, "No voting power");
+
+        if support {
+            proposal.votes_for += &weight;
+        } else {
+            proposal.votes_against += &weight;
+        }
+        self.proposals(proposal_id).set(proposal);
+        self.has_voted(proposal_id, &caller).set(true);
+        self.fee_vote_event(proposal_id, &caller, support, &weight);
+    }
+
+    /// Marks a passed proposal as executed and returns the fee to apply.
+    fn take_passed_fee_proposal(&self, proposal_id: u64) -> BigUint {
+        let mut proposal = self.get_existing_proposal(proposal_id);
+        require!(!proposal.executed, "Proposal already executed");
+        require!(
+            self.blockchain().get_block_timestamp() >= proposal.end,
+            "Voting period has not ended"
+        );
+        let quorum = &proposal.total_volume * proposal.quorum_bps / MAX_BPS;
+        require!(
+            &proposal.votes_for + &proposal.votes_against >= quorum,
+            "Quorum not reached"
+        );
+        require!(
+            proposal.votes_for > proposal.votes_against,
+            "Proposal did not pass"
+        );
+
+        proposal.executed = true;
+        self.proposals(proposal_id).set(&proposal);
+        self.fee_proposal_executed_event(proposal_id, &proposal.new_fee);
+
+        proposal.new_fee
+    }
+
+    /// Called for every deposit, with the full payment including the fee. Deposits to
+    /// oneself do not count, so voting power cannot be bought by cycling funds.
+    fn record_deposited_volume(
+        &self,
+        depositor: &ManagedAddress,
+        receiver: &ManagedAddress,
+        amount: &BigUint,
+    ) {
+        if depositor == receiver {
+            return;
+        }
+
+        let volume = self.deposited_volume(depositor).update(|volume| {
+            *volume += amount;
+            volume.clone()
+        });
+        self.total_deposited_volume()
+            .update(|volume| *volume += amount);
+
+        let proposal_id = self.last_proposal_id().get();
+        let checkpoint = VolumeCheckpoint {
+            proposal_id,
+            volume,
+        };
+        let mut checkpoints = self.volume_checkpoints(depositor);
+        let len = checkpoints.len();
+        if len > 0 && checkpoints.get(len).proposal_id == proposal_id {
+            checkpoints.set(len, &checkpoint);
+        } else {
+            checkpoints.push(&checkpoint);
+        }
+    }
+
+    /// The voter's volume from deposits made before the proposal was created.
+    #[view(getVotingWeight)]
+    fn get_voting_weight(&self, proposal_id: u64, voter: &ManagedAddress) -> BigUint {
+        let checkpoints = self.volume_checkpoints(voter);
+        // binary search for the last checkpoint taken before the proposal existed
+        let mut low = 1;
+        let mut high = checkpoints.len();
+        let mut weight = BigUint::zero();
+        while low <= high {
+            let mid = (low + high) / 2;
+            let checkpoint = checkpoints.get(mid);
+            if checkpoint.proposal_id < proposal_id {
+                weight = checkpoint.volume;
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+        weight
+    }
+
+    fn get_existing_proposal(&self, proposal_id: u64) -> FeeProposal<Self::Api> {
+        require!(
+            !self.proposals(proposal_id).is_empty(),
+            "Proposal not found"
+        );
+        self.proposals(proposal_id).get()
+    }
+
+    #[view(getProposal)]
+    fn get_proposal(&self, proposal_id: u64) -> OptionalValue<FeeProposal<Self::Api>> {
+        if self.proposals(proposal_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.proposals(proposal_id).get())
+    }
+
+    #[storage_mapper("proposals")]
+    fn proposals(&self, proposal_id: u64) -> SingleValueMapper<FeeProposal<Self::Api>>;
+
+    #[view(getLastProposalId)]
+    #[storage_mapper("lastProposalId")]
+    fn last_proposal_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(hasVoted)]
+    #[storage_mapper("hasVoted")]
+    fn has_voted(&self, proposal_id: u64, voter: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(getVotingPeriod)]
+    #[storage_mapper("votingPeriod")]
+    fn voting_period(&self) -> SingleValueMapper<u64>;
+
+    #[view(getQuorum)]
+    #[storage_mapper("quorumBps")]
+    fn quorum_bps(&self) -> SingleValueMapper<u32>;
+
+    #[view(getDepositedVolume)]
+    #[storage_mapper("depositedVolume")]
+    fn deposited_volume(&self, depositor: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("volumeCheckpoints")]
+    fn volume_checkpoints(
+        &self,
+        depositor: &ManagedAddress,
+    ) -> VecMapper<VolumeCheckpoint<Self::Api>>;
+
+    #[view(getTotalDepositedVolume)]
+    #[storage_mapper("totalDepositedVolume")]
+    fn total_deposited_volume(&self) -> SingleValueMapper<BigUint>;
+}
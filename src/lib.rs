@@ -1,45 +1,200 @@
 #![no_std]
 
 use multiversx_sc::imports::*;
+pub mod beneficiaries;
+pub mod circuit_breaker;
+pub mod claim_and_call;
+pub mod compliance;
+pub mod dormancy;
+pub mod escrow;
+pub mod events;
+pub mod fee_forwarding;
+pub mod governance;
+pub mod limits;
+pub mod membership;
+pub mod migration;
+pub mod operators;
+pub mod payouts;
+pub mod rate_limit;
+pub mod receipts;
+pub mod recovery;
+pub mod referrals;
+pub mod registration;
+pub mod reserves;
+pub mod roles;
 pub mod rust_challenge_proxy;
+pub mod signed_withdrawals;
+pub mod statistics;
+pub mod subscriptions;
+
+pub const MAX_MEMO_LEN: usize = 64;
 
 /// An empty contract. To be used as a template when starting a new contract from scratch.
 #[multiversx_sc::contract]
-pub trait RustChallenge {
+pub trait RustChallenge:
+    limits::LimitsModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + reserves::ReservesModule
+    + beneficiaries::BeneficiariesModule
+    + referrals::ReferralsModule
+    + events::EventsModule
+    + claim_and_call::ClaimAndCallModule
+    + payouts::PayoutsModule
+    + dormancy::DormancyModule
+    + migration::MigrationModule
+    + rate_limit::RateLimitModule
+    + circuit_breaker::CircuitBreakerModule
+    + recovery::RecoveryModule
+    + operators::OperatorsModule
+    + signed_withdrawals::SignedWithdrawalsModule
+    + escrow::EscrowModule
+    + subscriptions::SubscriptionsModule
+    + membership::MembershipModule
+    + governance::GovernanceModule
+    + fee_forwarding::FeeForwardingModule
+    + statistics::StatisticsModule
+    + receipts::ReceiptsModule
+{
     #[init]
     fn init(&self, fee: BigUint) {
         require!(fee >= BigUint::zero(), "Fee should be positive");
         self.set_fee(fee);
+        self.storage_version().set(migration::STORAGE_VERSION);
     }
 
     #[payable("EGLD")]
     #[endpoint]
-    fn deposit(&self, receiver: ManagedAddress) {
+    fn deposit(&self, receiver: ManagedAddress, opt_referrer: OptionalValue<ManagedAddress>) {
+        self.process_deposit(receiver, opt_referrer.into_option(), ManagedBuffer::new());
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(depositWithMemo)]
+    fn deposit_with_memo(
+        &self,
+        receiver: ManagedAddress,
+        memo: ManagedBuffer,
+        opt_referrer: OptionalValue<ManagedAddress>,
+    ) {
+        require!(memo.len() <= MAX_MEMO_LEN, "Memo too long");
+        self.process_deposit(receiver, opt_referrer.into_option(), memo);
+    }
+
+    fn process_deposit(
+        &self,
+        receiver: ManagedAddress,
+        opt_referrer: Option<ManagedAddress>,
+        memo: ManagedBuffer,
+    ) {
+        let caller = self.blockchain().get_caller();
         let payment_amount = self.call_value().egld_value();
         require!(
-            *payment_amount > self.get_fee(),
-            "Payments must be greater than fee"
+            self.is_address_permitted(&caller),
+            "Sender is not permitted"
         );
-        if self.collected_fees().is_empty() {
-            self.collected_fees().set(self.get_fee());
-        } else {
-            self.collected_fees().update(|fee| *fee += self.get_fee());
+        require!(
+            self.is_address_permitted(&receiver),
+            "Receiver is not permitted"
+        );
+        self.require_receiver_accepts_deposits(&receiver);
+        let mut fee = self.apply_membership_discount(&self.get_fee(), &caller);
+        require!(*payment_amount > fee, "Payments must be greater than fee");
+        let reserve = payment_amount.clone_value().sub(&fee);
+        self.require_deposit_within_limits(
+            &payment_amount,
+            &(self.get_reserve_for_address(&receiver) + &reserve),
+        );
+
+        if let Some(referrer) = opt_referrer {
+            fee -= self.reward_referrer(&referrer, &payment_amount, &fee);
         }
 
-        let reserve = payment_amount.clone_value().sub(self.get_fee());
+        self.collect_fee(&fee);
+        let collected_fees = self.get_collected_fees();
+        if self.is_fee_forwarding_due(&collected_fees) {
+            self.collected_fees().clear();
+            self.forward_fees(&collected_fees);
+        }
 
-        if self.reserve_for_address(&receiver).is_empty() {
-            self.reserve_for_address(&receiver).set(reserve);
+        if self.receipt_mode().get() {
+            self.mint_receipt(&caller, &receiver, &reserve);
         } else {
-            self.reserve_for_address(&receiver)
-                .update(|current_reserve| *current_reserve += reserve);
+            self.credit_reserve(&receiver, &reserve);
+            self.record_deposit(
+                &receiver,
+                &reserves::DepositRecord {
+                    sender: caller.clone(),
+                    amount: reserve.clone(),
+                    timestamp: self.blockchain().get_block_timestamp(),
+                    memo: memo.clone(),
+                },
+            );
         }
+        self.record_deposited_volume(&caller, &receiver, &payment_amount);
+        self.record_deposit_stats(&caller, &receiver, &payment_amount, &reserve);
+        self.deposit_event(&caller, &receiver, &reserve, &memo);
+    }
+
+    /// Moves `amount` from the caller's reserve to `to`, minus the transfer fee.
+    /// Counts against the caller's withdrawal allowance.
+    #[endpoint(transferReserve)]
+    fn transfer_reserve(&self, to: ManagedAddress, amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.is_address_permitted(&caller),
+            "Sender is not permitted"
+        );
+        require!(self.is_address_permitted(&to), "Receiver is not permitted");
+        require!(caller != to, "Cannot transfer to yourself");
+        self.require_receiver_accepts_deposits(&to);
+
+        let fee = self.transfer_fee().get();
+        require!(amount > fee, "Amount must be greater than transfer fee");
+        require!(
+            amount <= self.get_reserve_for_address(&caller),
+            "Insufficient reserve"
+        );
+        require!(
+            amount <= self.get_allowed_withdrawal(&caller, &amount),
+            "Withdrawal limit reached"
+        );
+        let received = &amount - &fee;
+        self.require_reserve_within_cap(&(self.get_reserve_for_address(&to) + &received));
+
+        self.touch_activity(&caller);
+        self.record_withdrawal(&caller, &amount);
+        self.debit_reserve(&caller, &amount);
+        self.collect_fee(&fee);
+        self.credit_reserve(&to, &received);
+        self.record_received_stats(&to, &received);
+        self.record_deposit(
+            &to,
+            &reserves::DepositRecord {
+                sender: caller.clone(),
+                amount: received.clone(),
+                timestamp: self.blockchain().get_block_timestamp(),
+                memo: ManagedBuffer::new(),
+            },
+        );
+        self.reserve_transfer_event(&caller, &to, &received, &fee);
     }
 
     #[endpoint]
     fn withdraw(&self) {
         let caller = self.blockchain().get_caller();
         let owner = self.blockchain().get_owner_address();
+        require!(
+            self.is_address_permitted(&caller),
+            "Caller is not permitted"
+        );
+        let payout_address = self.get_payout_address(&caller);
+        require!(
+            self.is_address_permitted(&payout_address),
+            "Payout address is not permitted"
+        );
+        self.require_withdrawals_not_paused();
         if !owner.eq(&caller) {
             require!(
                 !self.reserve_for_address(&caller).is_empty(),
@@ -47,9 +202,24 @@ pub trait RustChallenge {
             );
         }
 
-        let transfer_amount = self.get_reserve_for_address(&caller);
-        self.reserve_for_address(&caller).clear();
-        self.tx().to(&caller).egld(transfer_amount).transfer();
+        self.touch_activity(&caller);
+        let transfer_amount =
+            self.get_allowed_withdrawal(&caller, &self.get_reserve_for_address(&caller));
+        if !owner.eq(&caller) {
+            require!(transfer_amount > 0, "Withdrawal limit reached");
+        }
+        if transfer_amount > 0 {
+            if !self.record_outflow(&transfer_amount) {
+                return;
+            }
+            self.record_withdrawal(&caller, &transfer_amount);
+            self.debit_reserve(&caller, &transfer_amount);
+            self.record_withdrawal_stats(&caller, &transfer_amount);
+        }
+        self.tx()
+            .to(&payout_address)
+            .egld(transfer_amount)
+            .transfer();
 
         if owner.eq(&caller) {
             self.tx()
@@ -61,7 +231,9 @@ pub trait RustChallenge {
     }
 
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        self.run_migrations();
+    }
 
     #[storage_mapper]
     fn fee(&self) -> SingleValueMapper<BigUint>;
@@ -69,27 +241,89 @@ pub trait RustChallenge {
     #[only_owner]
     #[endpoint(setFee)]
     fn set_fee(&self, fee: BigUint) {
+        self.apply_fee(fee);
+    }
+
+    /// Shared by the owner's `setFee` and governance execution.
+    fn apply_fee(&self, fee: BigUint) {
         self.fee().set(fee);
     }
 
+    /// Applies a fee proposal that passed the depositors' vote. Callable by anyone.
+    #[endpoint(executeFeeProposal)]
+    fn execute_fee_proposal(&self, proposal_id: u64) {
+        let new_fee = self.take_passed_fee_proposal(proposal_id);
+        self.apply_fee(new_fee);
+    }
+
     #[view]
     fn get_fee(&self) -> BigUint {
         self.fee().get()
     }
 
-    #[storage_mapper("collectedFees")]
-    fn collected_fees(&self) -> SingleValueMapper<BigUint>;
+    /// Charged on `transferReserve`, separately from the deposit fee. May be zero.
+    #[storage_mapper("transferFee")]
+    fn transfer_fee(&self) -> SingleValueMapper<BigUint>;
 
-    #[storage_mapper("reserveForAddress")]
-    fn reserve_for_address(&self, receiver: &ManagedAddress) -> SingleValueMapper<BigUint>;
+    #[only_owner]
+    #[endpoint(setTransferFee)]
+    fn set_transfer_fee(&self, fee: BigUint) {
+        self.transfer_fee().set(fee);
+    }
+
+    #[view(getTransferFee)]
+    fn get_transfer_fee(&self) -> BigUint {
+        self.transfer_fee().get()
+    }
 
-    #[view(getReserveForAddress)]
-    fn get_reserve_for_address(&self, receiver: &ManagedAddress) -> BigUint {
-        self.reserve_for_address(receiver).get()
+    /// Splits a fee between the beneficiaries and the owner's collected fees.
+    fn collect_fee(&self, fee: &BigUint) {
+        let owner_fee = self.distribute_fee(fee);
+        if self.collected_fees().is_empty() {
+            self.collected_fees().set(owner_fee);
+        } else {
+            self.collected_fees().update(|fee| *fee += owner_fee);
+        }
     }
 
+    #[storage_mapper("collectedFees")]
+    fn collected_fees(&self) -> SingleValueMapper<BigUint>;
+
     #[view(getCollectedFees)]
     fn get_collected_fees(&self) -> BigUint {
         self.collected_fees().get()
     }
+
+    /// Reserves, owner and beneficiary fees, locked escrow tranches, prefunded subscriptions
+    /// and unredeemed receipts must be covered by the contract balance.
+    #[view(getSolvency)]
+    fn get_solvency(&self) -> reserves::SolvencyInfo<Self::Api> {
+        let total_reserves = self.total_reserves().get();
+        let collected_fees = self.get_collected_fees();
+        let accrued_fees = self.total_accrued_fees().get();
+        let total_escrowed = self.total_escrowed().get();
+        let subscription_balances = self.total_subscription_balances().get();
+        let outstanding_receipts = self.outstanding_receipts().get();
+        let balance = self
+            .blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::egld(), 0);
+        let is_solvent = &total_reserves
+            + &collected_fees
+            + &accrued_fees
+            + &total_escrowed
+            + &subscription_balances
+            + &outstanding_receipts
+            <= balance;
+
+        reserves::SolvencyInfo {
+            total_reserves,
+            collected_fees,
+            accrued_fees,
+            total_escrowed,
+            subscription_balances,
+            outstanding_receipts,
+            balance,
+            is_solvent,
+        }
+    }
 }
@@ -0,0 +1,90 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+/// Owner-configured bounds applied to every deposit. A zero maximum means "no limit".
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct DepositLimits<M: ManagedTypeApi> {
+    pub min_deposit: BigUint<M>,
+    pub max_deposit: BigUint<M>,
+    pub max_reserve_per_receiver: BigUint<M>,
+    pub max_total_value: BigUint<M>,
+}
+
+#[multiversx_sc::module]
+pub trait LimitsModule {
+    #[only_owner]
+    #[endpoint(setDepositLimits)]
+    fn set_deposit_limits(
+        &self,
+        min_deposit: BigUint,
+        max_deposit: BigUint,
+        max_reserve_per_receiver: BigUint,
+        max_total_value: BigUint,
+    ) {
+        require!(
+            max_deposit == 0 || min_deposit <= max_deposit,
+            "Minimum deposit exceeds maximum deposit"
+        );
+        self.deposit_limits().set(DepositLimits {
+            min_deposit,
+            max_deposit,
+            max_reserve_per_receiver,
+            max_total_value,
+        });
+    }
+
+    fn require_deposit_within_limits(&self, payment_amount: &BigUint, receiver_reserve: &BigUint) {
+        if self.deposit_limits().is_empty() {
+            return;
+        }
+        let limits = self.deposit_limits().get();
+
+        require!(
+            *payment_amount >= limits.min_deposit,
+            "Deposit below minimum"
+        );
+        require!(
+            limits.max_deposit == 0 || *payment_amount <= limits.max_deposit,
+            "Deposit above maximum"
+        );
+        self.require_reserve_within_cap(receiver_reserve);
+
+        // the incoming payment is already part of the balance at this point
+        let contract_balance = self
+            .blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::egld(), 0);
+        require!(
+            limits.max_total_value == 0 || contract_balance <= limits.max_total_value,
+            "Contract value cap exceeded"
+        );
+    }
+
+    /// For value that moves into a reserve from funds already held by the contract.
+    fn require_reserve_within_cap(&self, receiver_reserve: &BigUint) {
+        if self.deposit_limits().is_empty() {
+            return;
+        }
+        let max_reserve = self.deposit_limits().get().max_reserve_per_receiver;
+        require!(
+            max_reserve == 0 || *receiver_reserve <= max_reserve,
+            "Receiver reserve cap exceeded"
+        );
+    }
+
+    #[storage_mapper("depositLimits")]
+    fn deposit_limits(&self) -> SingleValueMapper<DepositLimits<Self::Api>>;
+
+    #[view(getDepositLimits)]
+    fn get_deposit_limits(&self) -> DepositLimits<Self::Api> {
+        if self.deposit_limits().is_empty() {
+            return DepositLimits {
+                min_deposit: BigUint::zero(),
+                max_deposit: BigUint::zero(),
+                max_reserve_per_receiver: BigUint::zero(),
+                max_total_value: BigUint::zero(),
+            };
+        }
+        self.deposit_limits().get()
+    }
+}
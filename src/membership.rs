@@ -0,0 +1,105 @@
+use multiversx_sc::imports::*;
+
+use crate::beneficiaries::MAX_BPS;
+
+/// Deposit fee discount for holders of a membership token, such as an NFT collection.
+///
+/// Holders prove membership by locking one unit of the token in the contract, which works
+/// across shards, and can take it back at any time.
+///
+/// Passing the token through `deposit` and returning it in the same call is not possible:
+/// this framework version cannot receive EGLD and ESDTs in one transfer, since multi-transfers
+/// carrying `EGLD-000000` are not supported, and `egld_value` fails when ESDTs are attached.
+/// Reading the sender's balance instead only works when the sender is on the contract's shard.
+#[multiversx_sc::module]
+pub trait MembershipModule {
+    /// A zero discount disables the membership discount.
+    #[only_owner]
+    #[endpoint(setMembershipDiscount)]
+    fn set_membership_discount(&self, token_id: TokenIdentifier, discount_bps: u32) {
+        require!(
+            token_id.is_valid_esdt_identifier(),
+            "Invalid token identifier"
+        );
+        require!(
+            discount_bps <= MAX_BPS,
+            "Discount cannot exceed 10000 basis points"
+        );
+        self.membership_token().set(token_id);
+        self.membership_discount().set(discount_bps);
+    }
+
+    #[payable("*")]
+    #[endpoint(lockMembershipToken)]
+    fn lock_membership_token(&self) {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        require!(
+            !self.membership_token().is_empty()
+                && payment.token_identifier == self.membership_token().get(),
+            "Not the membership token"
+        );
+        require!(payment.amount == 1u32, "Lock exactly one membership token");
+        require!(
+            self.locked_membership(&caller).is_empty(),
+            "Membership token already locked"
+        );
+
+        self.locked_membership(&caller).set(payment);
+    }
+
+    #[endpoint(unlockMembershipToken)]
+    fn unlock_membership_token(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.locked_membership(&caller).is_empty(),
+            "No membership token locked"
+        );
+
+        let payment = self.locked_membership(&caller).take();
+        self.tx().to(&caller).payment(payment).transfer();
+    }
+
+    /// Returns `fee` reduced by the membership discount if `holder` has the current
+    /// membership token locked.
+    fn apply_membership_discount(&self, fee: &BigUint, holder: &ManagedAddress) -> BigUint {
+        let discount_bps = self.membership_discount().get();
+        if discount_bps == 0 || self.locked_membership(holder).is_empty() {
+            return fee.clone();
+        }
+        if self.locked_membership(holder).get().token_identifier != self.membership_token().get() {
+            return fee.clone();
+        }
+
+        fee - &(fee * discount_bps / MAX_BPS)
+    }
+
+    #[storage_mapper("membershipToken")]
+    fn membership_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getMembershipToken)]
+    fn get_membership_token(&self) -> OptionalValue<TokenIdentifier> {
+        if self.membership_token().is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.membership_token().get())
+    }
+
+    #[view(getMembershipDiscount)]
+    #[storage_mapper("membershipDiscount")]
+    fn membership_discount(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("lockedMembership")]
+    fn locked_membership(&self, holder: &ManagedAddress) -> SingleValueMapper<EsdtTokenPayment>;
+
+    #[view(getLockedMembershipToken)]
+    fn get_locked_membership_token(
+        &self,
+        holder: ManagedAddress,
+    ) -> OptionalValue<EsdtTokenPayment> {
+        if self.locked_membership(&holder).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.locked_membership(&holder).get())
+    }
+}
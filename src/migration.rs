@@ -0,0 +1,73 @@
+use multiversx_sc::imports::*;
+
+use crate::reserves;
+
+/// Layout written by the current code. Version 0 is the original layout, which only had
+/// `fee`, `collectedFees` and `reserveForAddress`.
+pub const STORAGE_VERSION: u32 = 1;
+pub const MAX_MIGRATION_BATCH: usize = 100;
+
+/// Brings storage written by older code up to the current layout, one version at a time.
+#[multiversx_sc::module]
+pub trait MigrationModule: reserves::ReservesModule {
+    /// Runs every migration step that can complete inside the upgrade transaction. A step
+    /// that needs batches leaves the migration pending until `completeMigration` is called.
+    fn run_migrations(&self) {
+        let mut version = self.storage_version().get();
+        require!(
+            version <= STORAGE_VERSION,
+            "Cannot downgrade the storage layout"
+        );
+
+        while version < STORAGE_VERSION {
+            if !self.run_migration_step(version) {
+                self.migration_pending().set(true);
+                return;
+            }
+            version += 1;
+            self.storage_version().set(version);
+        }
+    }
+
+    /// Returns `false` if the step from `version` needs further batched calls.
+    fn run_migration_step(&self, version: u32) -> bool {
+        match version {
+            // reserves must be indexed in batches, see `migrateReserves`
+            0 => false,
+            _ => sc_panic!("Unknown storage version"),
+        }
+    }
+
+    /// Version 0 to 1: indexes the reserves of the given addresses into the receivers set and
+    /// the running reserve total. Addresses that are already indexed are skipped.
+    #[only_owner]
+    #[endpoint(migrateReserves)]
+    fn migrate_reserves(&self, addresses: MultiValueEncoded<ManagedAddress>) {
+        require!(self.migration_pending().get(), "No migration pending");
+        require!(
+            self.storage_version().get() == 0,
+            "Reserves are already migrated"
+        );
+        require!(addresses.len() <= MAX_MIGRATION_BATCH, "Batch too large");
+
+        for address in addresses {
+            self.index_reserve(&address);
+        }
+    }
+
+    /// Marks the batched step as done and runs the remaining steps, if any. Reserves that
+    /// were not passed to `migrateReserves` are indexed the next time they are credited or
+    /// debited.
+    #[only_owner]
+    #[endpoint(completeMigration)]
+    fn complete_migration(&self) {
+        require!(self.migration_pending().get(), "No migration pending");
+        self.storage_version().update(|version| *version += 1);
+        self.migration_pending().clear();
+        self.run_migrations();
+    }
+
+    #[view(getStorageVersion)]
+    #[storage_mapper("storageVersion")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+}
@@ -0,0 +1,164 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::{
+    circuit_breaker, compliance, events, rate_limit, registration, reserves, roles, statistics,
+};
+
+/// A zero allowance means unlimited, a zero expiry means the approval never expires.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct OperatorApproval<M: ManagedTypeApi> {
+    pub allowance: BigUint<M>,
+    pub expiry: u64,
+}
+
+/// Lets receivers approve operators, such as custodial partners, to withdraw on their behalf.
+#[multiversx_sc::module]
+pub trait OperatorsModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + events::EventsModule
+    + rate_limit::RateLimitModule
+    + circuit_breaker::CircuitBreakerModule
+    + statistics::StatisticsModule
+{
+    #[endpoint(approveOperator)]
+    fn approve_operator(&self, operator: ManagedAddress, allowance: BigUint, expiry: u64) {
+        let caller = self.blockchain().get_caller();
+        require!(operator != caller, "Cannot approve yourself");
+
+        self.touch_activity(&caller);
+        self.operator_approvals(&caller)
+            .insert(operator, OperatorApproval { allowance, expiry });
+    }
+
+    #[endpoint(revokeOperator)]
+    fn revoke_operator(&self, operator: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.operator_approvals(&caller).remove(&operator).is_some(),
+            "Operator not approved"
+        );
+        self.touch_activity(&caller);
+    }
+
+    #[endpoint(approveDestination)]
+    fn approve_destination(&self, destination: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        self.touch_activity(&caller);
+        self.approved_destinations(&caller).insert(destination);
+    }
+
+    #[endpoint(revokeDestination)]
+    fn revoke_destination(&self, destination: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.approved_destinations(&caller)
+                .swap_remove(&destination),
+            "Destination not approved"
+        );
+        self.touch_activity(&caller);
+    }
+
+    /// Called by an approved operator. Pays `amount` from the receiver's reserve to the
+    /// receiver's payout address, or to a destination the receiver approved.
+    #[endpoint(withdrawFor)]
+    fn withdraw_for(
+        &self,
+        receiver: ManagedAddress,
+        amount: BigUint,
+        opt_destination: OptionalValue<ManagedAddress>,
+    ) {
+        let operator = self.blockchain().get_caller();
+        let mut approval = match self.operator_approvals(&receiver).get(&operator) {
+            Some(approval) => approval,
+            None => sc_panic!("Operator not approved"),
+        };
+        require!(
+            approval.expiry == 0 || self.blockchain().get_block_timestamp() < approval.expiry,
+            "Approval expired"
+        );
+        require!(amount > 0, "Amount must be greater than zero");
+        require!(
+            approval.allowance == 0 || amount <= approval.allowance,
+            "Amount exceeds operator allowance"
+        );
+
+        let destination = match opt_destination {
+            OptionalValue::Some(destination) => {
+                require!(
+                    destination == receiver
+                        || self.approved_destinations(&receiver).contains(&destination),
+                    "Destination not approved by receiver"
+                );
+                destination
+            }
+            OptionalValue::None => self.get_payout_address(&receiver),
+        };
+
+        require!(
+            self.is_address_permitted(&operator)
+                && self.is_address_permitted(&receiver)
+                && self.is_address_permitted(&destination),
+            "Caller is not permitted"
+        );
+        self.require_withdrawals_not_paused();
+        require!(
+            amount <= self.get_reserve_for_address(&receiver),
+            "Insufficient reserve"
+        );
+        require!(
+            amount <= self.get_allowed_withdrawal(&receiver, &amount),
+            "Withdrawal limit reached"
+        );
+        if !self.record_outflow(&amount) {
+            return;
+        }
+        self.record_withdrawal(&receiver, &amount);
+        self.debit_reserve(&receiver, &amount);
+        self.record_withdrawal_stats(&receiver, &amount);
+
+        if approval.allowance > 0 {
+            approval.allowance -= &amount;
+            if approval.allowance == 0 {
+                self.operator_approvals(&receiver).remove(&operator);
+            } else {
+                self.operator_approvals(&receiver)
+                    .insert(operator.clone(), approval);
+            }
+        }
+
+        self.tx().to(&destination).egld(&amount).transfer();
+        self.operator_withdrawal_event(&receiver, &operator, &destination, &amount);
+    }
+
+    #[view(getOperatorApproval)]
+    fn get_operator_approval(
+        &self,
+        receiver: &ManagedAddress,
+        operator: &ManagedAddress,
+    ) -> OptionalValue<OperatorApproval<Self::Api>> {
+        self.operator_approvals(receiver).get(operator).into()
+    }
+
+    #[view(getOperators)]
+    fn get_operators(&self, receiver: &ManagedAddress) -> MultiValueEncoded<ManagedAddress> {
+        self.operator_approvals(receiver).keys().collect()
+    }
+
+    #[view(getApprovedDestinations)]
+    #[storage_mapper("approvedDestinations")]
+    fn approved_destinations(
+        &self,
+        receiver: &ManagedAddress,
+    ) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("operatorApprovals")]
+    fn operator_approvals(
+        &self,
+        receiver: &ManagedAddress,
+    ) -> MapMapper<ManagedAddress, OperatorApproval<Self::Api>>;
+}
@@ -0,0 +1,75 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::roles::{self, Role};
+use crate::{circuit_breaker, compliance, events, rate_limit, registration, reserves, statistics};
+
+pub const MIN_GAS_PER_PAYOUT: u64 = 2_000_000;
+
+/// Like `OperationCompletionStatus`, plus a distinct status for a run that was ended by
+/// the circuit breaker. Such a run cannot be resumed until withdrawals are unpaused.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PayoutStatus {
+    Completed,
+    InterruptedBeforeOutOfGas,
+    CircuitBreakerTripped,
+}
+
+/// Lets the owner or a keeper push reserves to receivers that never call `withdraw`.
+#[multiversx_sc::module]
+pub trait PayoutsModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + rate_limit::RateLimitModule
+    + events::EventsModule
+    + circuit_breaker::CircuitBreakerModule
+    + statistics::StatisticsModule
+{
+    /// Pays out the given receivers in order, skipping frozen ones, those without a reserve
+    /// and those without withdrawal allowance left. Stops before running out of gas, or when
+    /// the circuit breaker trips, and returns how many receivers were processed, so the next
+    /// call can resume from there once the status allows it.
+    #[endpoint(pushPayouts)]
+    fn push_payouts(
+        &self,
+        receivers: MultiValueEncoded<ManagedAddress>,
+    ) -> MultiValue2<PayoutStatus, usize> {
+        self.require_owner_or_role(Role::Keeper);
+        self.require_withdrawals_not_paused();
+
+        let mut processed = 0usize;
+        for receiver in receivers {
+            if self.blockchain().get_gas_left() < MIN_GAS_PER_PAYOUT {
+                return (PayoutStatus::InterruptedBeforeOutOfGas, processed).into();
+            }
+            processed += 1;
+
+            let payout_address = self.get_payout_address(&receiver);
+            if !self.is_address_permitted(&receiver) || !self.is_address_permitted(&payout_address)
+            {
+                continue;
+            }
+            let reserve = self.get_reserve_for_address(&receiver);
+            if reserve == 0 {
+                continue;
+            }
+            let amount = self.get_allowed_withdrawal(&receiver, &reserve);
+            if amount == 0 {
+                continue;
+            }
+            if !self.record_outflow(&amount) {
+                return (PayoutStatus::CircuitBreakerTripped, processed - 1).into();
+            }
+
+            self.record_withdrawal(&receiver, &amount);
+            self.debit_reserve(&receiver, &amount);
+            self.record_withdrawal_stats(&receiver, &amount);
+            self.tx().to(&payout_address).egld(amount).transfer();
+        }
+
+        (PayoutStatus::Completed, processed).into()
+    }
+}
@@ -0,0 +1,97 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct WithdrawalWindow<M: ManagedTypeApi> {
+    pub start: u64,
+    pub withdrawn: BigUint<M>,
+}
+
+/// Caps how much each account can withdraw per time window. The window of an account
+/// starts with its first withdrawal after the previous window has ended.
+#[multiversx_sc::module]
+pub trait RateLimitModule {
+    /// A zero maximum disables the limit.
+    #[only_owner]
+    #[endpoint(setWithdrawalRateLimit)]
+    fn set_withdrawal_rate_limit(&self, max_amount: BigUint, window: u64) {
+        require!(
+            max_amount == 0 || window > 0,
+            "Window must be greater than zero"
+        );
+        self.max_withdrawal_per_window().set(max_amount);
+        self.withdrawal_window_length().set(window);
+    }
+
+    /// Returns how much of `requested` the address may withdraw in its current window.
+    fn get_allowed_withdrawal(&self, address: &ManagedAddress, requested: &BigUint) -> BigUint {
+        match self.get_remaining_allowance(address) {
+            OptionalValue::Some(remaining) if remaining < *requested => remaining,
+            _ => requested.clone(),
+        }
+    }
+
+    fn record_withdrawal(&self, address: &ManagedAddress, amount: &BigUint) {
+        if self.max_withdrawal_per_window().get() == 0 {
+            return;
+        }
+        let mut window = self.get_current_window(address);
+        window.withdrawn += amount;
+        self.withdrawal_window(address).set(window);
+    }
+
+    /// Gives back an amount recorded by `record_withdrawal` that never left the contract.
+    /// Nothing is given back once the window it was recorded in has ended.
+    fn release_withdrawal(&self, address: &ManagedAddress, amount: &BigUint) {
+        let mut window = self.get_current_window(address);
+        if window.withdrawn == 0 {
+            return;
+        }
+        window.withdrawn -= core::cmp::min(amount, &window.withdrawn).clone();
+        self.withdrawal_window(address).set(window);
+    }
+
+    fn get_current_window(&self, address: &ManagedAddress) -> WithdrawalWindow<Self::Api> {
+        let now = self.blockchain().get_block_timestamp();
+        if !self.withdrawal_window(address).is_empty() {
+            let window = self.withdrawal_window(address).get();
+            if now < window.start + self.withdrawal_window_length().get() {
+                return window;
+            }
+        }
+
+        WithdrawalWindow {
+            start: now,
+            withdrawn: BigUint::zero(),
+        }
+    }
+
+    /// Amount the address can still withdraw in its current window, if a limit is set.
+    #[view(getRemainingAllowance)]
+    fn get_remaining_allowance(&self, address: &ManagedAddress) -> OptionalValue<BigUint> {
+        let max_amount = self.max_withdrawal_per_window().get();
+        if max_amount == 0 {
+            return OptionalValue::None;
+        }
+        let withdrawn = self.get_current_window(address).withdrawn;
+        if withdrawn >= max_amount {
+            return OptionalValue::Some(BigUint::zero());
+        }
+        OptionalValue::Some(max_amount - withdrawn)
+    }
+
+    #[view(getMaxWithdrawalPerWindow)]
+    #[storage_mapper("maxWithdrawalPerWindow")]
+    fn max_withdrawal_per_window(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getWithdrawalWindowLength)]
+    #[storage_mapper("withdrawalWindowLength")]
+    fn withdrawal_window_length(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("withdrawalWindow")]
+    fn withdrawal_window(
+        &self,
+        address: &ManagedAddress,
+    ) -> SingleValueMapper<WithdrawalWindow<Self::Api>>;
+}
@@ -0,0 +1,140 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::{compliance, events, limits, registration, reserves, roles};
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ReceiptAttributes<M: ManagedTypeApi> {
+    pub amount: BigUint<M>,
+    pub depositor: ManagedAddress<M>,
+    pub timestamp: u64,
+}
+
+/// Receipt mode: instead of crediting the receiver's reserve, a deposit mints a receipt NFT
+/// to the receiver. Whoever holds the NFT can redeem it into their own reserve.
+#[multiversx_sc::module]
+pub trait ReceiptsModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + limits::LimitsModule
+    + events::EventsModule
+{
+    /// Issues the receipt collection with create and burn roles for this contract.
+    /// The payment covers the issue cost and is returned if issuing fails.
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issueReceiptToken)]
+    fn issue_receipt_token(&self, token_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        require!(
+            self.receipt_token().is_empty(),
+            "Receipt token already issued"
+        );
+        let issue_cost = self.call_value().egld_value().clone_value();
+        let owner = self.blockchain().get_caller();
+
+        self.receipt_token().issue_and_set_all_roles(
+            EsdtTokenType::NonFungible,
+            issue_cost,
+            token_name,
+            token_ticker,
+            0,
+            Some(self.callbacks().receipt_token_issue_callback(&owner)),
+        );
+    }
+
+    #[callback]
+    fn receipt_token_issue_callback(
+        &self,
+        owner: &ManagedAddress,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.receipt_token().set_token_id(token_id);
+            }
+            ManagedAsyncCallResult::Err(_) => {
+                self.receipt_token().clear();
+                let returned = self.call_value().egld_value().clone_value();
+                if returned > 0 {
+                    self.tx().to(owner).egld(&returned).transfer();
+                }
+            }
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(setReceiptMode)]
+    fn set_receipt_mode(&self, enabled: bool) {
+        require!(
+            !enabled || self.receipt_token().get_token_state().is_set(),
+            "Receipt token not issued"
+        );
+        self.receipt_mode().set(enabled);
+    }
+
+    fn mint_receipt(
+        &self,
+        depositor: &ManagedAddress,
+        receiver: &ManagedAddress,
+        amount: &BigUint,
+    ) {
+        self.require_no_pending_migration();
+        let attributes = ReceiptAttributes {
+            amount: amount.clone(),
+            depositor: depositor.clone(),
+            timestamp: self.blockchain().get_block_timestamp(),
+        };
+        let receipt =
+            self.receipt_token()
+                .nft_create_and_send(receiver, BigUint::from(1u32), &attributes);
+        self.outstanding_receipts()
+            .update(|outstanding| *outstanding += amount);
+        self.receipt_minted_event(receiver, receipt.token_nonce, amount);
+    }
+
+    /// Burns the receipt sent with the call and credits its amount to the caller's reserve.
+    #[payable("*")]
+    #[endpoint(redeemReceipt)]
+    fn redeem_receipt(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.is_address_permitted(&caller),
+            "Caller is not permitted"
+        );
+        // the redeemer's reserve is credited like a deposit
+        self.require_receiver_accepts_deposits(&caller);
+        let payment = self.call_value().single_esdt();
+        self.receipt_token()
+            .require_same_token(&payment.token_identifier);
+
+        let attributes: ReceiptAttributes<Self::Api> = self
+            .receipt_token()
+            .get_token_attributes(payment.token_nonce);
+        self.require_reserve_within_cap(
+            &(self.get_reserve_for_address(&caller) + &attributes.amount),
+        );
+        self.receipt_token()
+            .nft_burn(payment.token_nonce, &payment.amount);
+        self.outstanding_receipts()
+            .update(|outstanding| *outstanding -= &attributes.amount);
+
+        self.credit_reserve(&caller, &attributes.amount);
+        self.receipt_redeemed_event(&caller, payment.token_nonce, &attributes.amount);
+    }
+
+    #[view(getReceiptToken)]
+    #[storage_mapper("receiptToken")]
+    fn receipt_token(&self) -> NonFungibleTokenMapper;
+
+    #[view(isReceiptModeEnabled)]
+    #[storage_mapper("receiptMode")]
+    fn receipt_mode(&self) -> SingleValueMapper<bool>;
+
+    /// Sum of the amounts of all receipts not yet redeemed.
+    #[view(getOutstandingReceipts)]
+    #[storage_mapper("outstandingReceipts")]
+    fn outstanding_receipts(&self) -> SingleValueMapper<BigUint>;
+}
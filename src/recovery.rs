@@ -0,0 +1,114 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::{circuit_breaker, compliance, events, rate_limit, reserves, roles, statistics};
+
+/// Shortest waiting period a receiver can pick, 30 days.
+pub const MIN_RECOVERY_WAITING_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct RecoveryNomination<M: ManagedTypeApi> {
+    pub recovery_address: ManagedAddress<M>,
+    pub waiting_period: u64,
+}
+
+/// Lets a receiver nominate an address that can claim the reserve after a long inactivity.
+#[multiversx_sc::module]
+pub trait RecoveryModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + events::EventsModule
+    + rate_limit::RateLimitModule
+    + circuit_breaker::CircuitBreakerModule
+    + statistics::StatisticsModule
+{
+    #[endpoint(nominateRecoveryAddress)]
+    fn nominate_recovery_address(&self, recovery_address: ManagedAddress, waiting_period: u64) {
+        let caller = self.blockchain().get_caller();
+        require!(recovery_address != caller, "Cannot nominate yourself");
+        require!(
+            waiting_period >= MIN_RECOVERY_WAITING_PERIOD,
+            "Waiting period too short"
+        );
+
+        self.touch_activity(&caller);
+        self.recovery_nomination(&caller).set(RecoveryNomination {
+            recovery_address,
+            waiting_period,
+        });
+    }
+
+    #[endpoint(cancelRecoveryNomination)]
+    fn cancel_recovery_nomination(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.recovery_nomination(&caller).is_empty(),
+            "No recovery nomination"
+        );
+
+        self.touch_activity(&caller);
+        self.recovery_nomination(&caller).clear();
+    }
+
+    /// Called by the nominated address once the receiver has been inactive for the whole
+    /// waiting period. Pays the reserve, up to the withdrawal allowance, to the caller.
+    #[endpoint(claimAsRecovery)]
+    fn claim_as_recovery(&self, receiver: ManagedAddress) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.recovery_nomination(&receiver).is_empty(),
+            "No recovery nomination"
+        );
+        let nomination = self.recovery_nomination(&receiver).get();
+        require!(
+            nomination.recovery_address == caller,
+            "Caller is not the recovery address"
+        );
+        require!(
+            self.is_address_permitted(&caller) && self.is_address_permitted(&receiver),
+            "Caller is not permitted"
+        );
+        self.require_withdrawals_not_paused();
+        require!(
+            self.blockchain().get_block_timestamp()
+                >= self
+                    .last_activity(&receiver)
+                    .get()
+                    .saturating_add(nomination.waiting_period),
+            "Receiver is not inactive long enough"
+        );
+
+        let reserve = self.get_reserve_for_address(&receiver);
+        require!(reserve > 0, "Nothing to claim");
+        let amount = self.get_allowed_withdrawal(&receiver, &reserve);
+        require!(amount > 0, "Withdrawal limit reached");
+        if !self.record_outflow(&amount) {
+            return;
+        }
+        self.record_withdrawal(&receiver, &amount);
+        self.debit_reserve(&receiver, &amount);
+        self.record_withdrawal_stats(&receiver, &amount);
+
+        self.tx().to(&caller).egld(&amount).transfer();
+        self.reserve_recovered_event(&receiver, &caller, &amount);
+    }
+
+    #[view(getRecoveryNomination)]
+    fn get_recovery_nomination(
+        &self,
+        receiver: &ManagedAddress,
+    ) -> OptionalValue<RecoveryNomination<Self::Api>> {
+        if self.recovery_nomination(receiver).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.recovery_nomination(receiver).get())
+    }
+
+    #[storage_mapper("recoveryNomination")]
+    fn recovery_nomination(
+        &self,
+        receiver: &ManagedAddress,
+    ) -> SingleValueMapper<RecoveryNomination<Self::Api>>;
+}
@@ -0,0 +1,67 @@
+use multiversx_sc::imports::*;
+
+use crate::beneficiaries::MAX_BPS;
+use crate::{compliance, limits, registration, reserves, roles};
+
+/// Rewards integrators with a share of the fee on every deposit they refer.
+#[multiversx_sc::module]
+pub trait ReferralsModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + limits::LimitsModule
+{
+    #[only_owner]
+    #[endpoint(setReferralShare)]
+    fn set_referral_share(&self, share_bps: u32) {
+        require!(
+            share_bps <= MAX_BPS,
+            "Share cannot exceed 10000 basis points"
+        );
+        self.referral_share_bps().set(share_bps);
+    }
+
+    /// Credits the referrer's part of `fee` to its reserve and returns it.
+    fn reward_referrer(
+        &self,
+        referrer: &ManagedAddress,
+        payment_amount: &BigUint,
+        fee: &BigUint,
+    ) -> BigUint {
+        require!(
+            *referrer != self.blockchain().get_caller(),
+            "Cannot refer yourself"
+        );
+        require!(
+            self.is_address_permitted(referrer),
+            "Referrer is not permitted"
+        );
+        // the reward is credited like a deposit, so the same receiver rules apply
+        self.require_receiver_accepts_deposits(referrer);
+
+        let reward = fee * self.referral_share_bps().get() / MAX_BPS;
+        if reward > 0 {
+            self.require_reserve_within_cap(&(self.get_reserve_for_address(referrer) + &reward));
+            self.credit_reserve(referrer, &reward);
+            self.referral_earnings(referrer)
+                .update(|earnings| *earnings += &reward);
+        }
+        self.referred_volume(referrer)
+            .update(|volume| *volume += payment_amount);
+
+        reward
+    }
+
+    #[view(getReferralShare)]
+    #[storage_mapper("referralShareBps")]
+    fn referral_share_bps(&self) -> SingleValueMapper<u32>;
+
+    #[view(getReferralEarnings)]
+    #[storage_mapper("referralEarnings")]
+    fn referral_earnings(&self, referrer: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getReferredVolume)]
+    #[storage_mapper("referredVolume")]
+    fn referred_volume(&self, referrer: &ManagedAddress) -> SingleValueMapper<BigUint>;
+}
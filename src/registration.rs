@@ -0,0 +1,87 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+pub const MAX_DISPLAY_NAME_LEN: usize = 64;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ReceiverInfo<M: ManagedTypeApi> {
+    pub display_name: ManagedBuffer<M>,
+    pub payout_address: ManagedAddress<M>,
+}
+
+/// Optional opt-in registration that receivers must complete before accepting deposits.
+#[multiversx_sc::module]
+pub trait RegistrationModule {
+    #[only_owner]
+    #[endpoint(setRegistrationRequired)]
+    fn set_registration_required(&self, required: bool) {
+        self.registration_required().set(required);
+    }
+
+    #[endpoint]
+    fn register(
+        &self,
+        display_name: ManagedBuffer,
+        opt_payout_address: OptionalValue<ManagedAddress>,
+    ) {
+        require!(
+            display_name.len() <= MAX_DISPLAY_NAME_LEN,
+            "Display name too long"
+        );
+        let caller = self.blockchain().get_caller();
+        let payout_address = match opt_payout_address {
+            OptionalValue::Some(address) => address,
+            OptionalValue::None => caller.clone(),
+        };
+
+        self.receiver_info(&caller).set(ReceiverInfo {
+            display_name,
+            payout_address,
+        });
+    }
+
+    #[endpoint]
+    fn unregister(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.receiver_info(&caller).is_empty(), "Not registered");
+        self.receiver_info(&caller).clear();
+    }
+
+    fn require_receiver_accepts_deposits(&self, receiver: &ManagedAddress) {
+        if self.registration_required().get() {
+            require!(
+                !self.receiver_info(receiver).is_empty(),
+                "Receiver is not registered"
+            );
+        }
+    }
+
+    fn get_payout_address(&self, receiver: &ManagedAddress) -> ManagedAddress {
+        if self.receiver_info(receiver).is_empty() {
+            return receiver.clone();
+        }
+        self.receiver_info(receiver).get().payout_address
+    }
+
+    #[view(getReceiverInfo)]
+    fn get_receiver_info(
+        &self,
+        receiver: &ManagedAddress,
+    ) -> OptionalValue<ReceiverInfo<Self::Api>> {
+        if self.receiver_info(receiver).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.receiver_info(receiver).get())
+    }
+
+    #[view(isRegistrationRequired)]
+    #[storage_mapper("registrationRequired")]
+    fn registration_required(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("receiverInfo")]
+    fn receiver_info(
+        &self,
+        receiver: &ManagedAddress,
+    ) -> SingleValueMapper<ReceiverInfo<Self::Api>>;
+}
@@ -0,0 +1,180 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct SolvencyInfo<M: ManagedTypeApi> {
+    pub total_reserves: BigUint<M>,
+    pub collected_fees: BigUint<M>,
+    pub accrued_fees: BigUint<M>,
+    pub total_escrowed: BigUint<M>,
+    pub subscription_balances: BigUint<M>,
+    pub outstanding_receipts: BigUint<M>,
+    pub balance: BigUint<M>,
+    pub is_solvent: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct DepositRecord<M: ManagedTypeApi> {
+    pub sender: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub timestamp: u64,
+    pub memo: ManagedBuffer<M>,
+}
+
+/// Number of deposit records kept per receiver. Older records are overwritten.
+pub const MAX_DEPOSIT_RECORDS: usize = 100;
+
+/// Per-receiver reserves, plus the set of receivers currently holding a non-zero reserve.
+#[multiversx_sc::module]
+pub trait ReservesModule {
+    fn credit_reserve(&self, receiver: &ManagedAddress, amount: &BigUint) {
+        self.require_no_pending_migration();
+        self.index_reserve(receiver);
+        if self.reserve_for_address(receiver).is_empty() {
+            // a reserve going from zero to non-zero restarts the dormancy clock
+            self.reserve_for_address(receiver).set(amount);
+            self.touch_activity(receiver);
+        } else {
+            self.reserve_for_address(receiver)
+                .update(|current_reserve| *current_reserve += amount);
+        }
+        self.receivers().insert(receiver.clone());
+        self.total_reserves().update(|total| *total += amount);
+    }
+
+    fn debit_reserve(&self, receiver: &ManagedAddress, amount: &BigUint) {
+        self.require_no_pending_migration();
+        self.index_reserve(receiver);
+        let remaining = self
+            .reserve_for_address(receiver)
+            .update(|current_reserve| {
+                require!(*current_reserve >= *amount, "Insufficient reserve");
+                *current_reserve -= amount;
+                current_reserve.clone()
+            });
+        if remaining == 0 {
+            self.reserve_for_address(receiver).clear();
+            self.receivers().swap_remove(receiver);
+        }
+        self.total_reserves().update(|total| *total -= amount);
+    }
+
+    /// Adds a reserve written by the version 0 layout to the receivers set and the running
+    /// total, in case `migrateReserves` missed it. Its dormancy clock starts now.
+    fn index_reserve(&self, receiver: &ManagedAddress) {
+        if self.reserve_for_address(receiver).is_empty() || self.receivers().contains(receiver) {
+            return;
+        }
+
+        let reserve = self.reserve_for_address(receiver).get();
+        self.receivers().insert(receiver.clone());
+        self.total_reserves().update(|total| *total += &reserve);
+        if self.last_activity(receiver).is_empty() {
+            self.touch_activity(receiver);
+        }
+    }
+
+    fn require_no_pending_migration(&self) {
+        require!(
+            !self.migration_pending().get(),
+            "Storage migration in progress"
+        );
+    }
+
+    #[view(isMigrationPending)]
+    #[storage_mapper("migrationPending")]
+    fn migration_pending(&self) -> SingleValueMapper<bool>;
+
+    fn touch_activity(&self, address: &ManagedAddress) {
+        self.last_activity(address)
+            .set(self.blockchain().get_block_timestamp());
+    }
+
+    /// Timestamp of the address' last own action, or of when its reserve last became non-zero.
+    #[view(getLastActivity)]
+    #[storage_mapper("lastActivity")]
+    fn last_activity(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("reserveForAddress")]
+    fn reserve_for_address(&self, receiver: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getReserveForAddress)]
+    fn get_reserve_for_address(&self, receiver: &ManagedAddress) -> BigUint {
+        self.reserve_for_address(receiver).get()
+    }
+
+    #[view(getTotalReserves)]
+    #[storage_mapper("totalReserves")]
+    fn total_reserves(&self) -> SingleValueMapper<BigUint>;
+
+    /// Stores a deposit record, overwriting the oldest one once the receiver has
+    /// `MAX_DEPOSIT_RECORDS` of them.
+    fn record_deposit(&self, receiver: &ManagedAddress, record: &DepositRecord<Self::Api>) {
+        let mut records = self.deposit_history(receiver);
+        if records.len() < MAX_DEPOSIT_RECORDS {
+            records.push(record);
+            return;
+        }
+
+        let oldest = self.oldest_deposit_record(receiver).get();
+        records.set(oldest + 1, record);
+        self.oldest_deposit_record(receiver)
+            .set((oldest + 1) % records.len());
+    }
+
+    /// The receiver's most recent deposits, at most `MAX_DEPOSIT_RECORDS` of them. Records are
+    /// kept after the reserve is withdrawn.
+    #[storage_mapper("depositHistory")]
+    fn deposit_history(&self, receiver: &ManagedAddress) -> VecMapper<DepositRecord<Self::Api>>;
+
+    /// Zero-based position of the oldest record once the buffer has wrapped around.
+    #[storage_mapper("oldestDepositRecord")]
+    fn oldest_deposit_record(&self, receiver: &ManagedAddress) -> SingleValueMapper<usize>;
+
+    /// Returns up to `size` records of the receiver's deposit history, oldest first, starting at
+    /// the zero-based index `from`. Deposits that were already withdrawn are included.
+    #[view(getDepositHistory)]
+    fn get_deposit_history(
+        &self,
+        receiver: &ManagedAddress,
+        from: usize,
+        size: usize,
+    ) -> MultiValueEncoded<DepositRecord<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        let records = self.deposit_history(receiver);
+        let oldest = self.oldest_deposit_record(receiver).get();
+        let end = core::cmp::min(from.saturating_add(size), records.len());
+        for index in from..end {
+            result.push(records.get((oldest + index) % records.len() + 1));
+        }
+        result
+    }
+
+    #[storage_mapper("receivers")]
+    fn receivers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getReceiversCount)]
+    fn get_receivers_count(&self) -> usize {
+        self.receivers().len()
+    }
+
+    /// Returns up to `size` (address, reserve) pairs, starting at the zero-based index `from`.
+    #[view(getReceivers)]
+    fn get_receivers(
+        &self,
+        from: usize,
+        size: usize,
+    ) -> MultiValueEncoded<MultiValue2<ManagedAddress, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        let receivers = self.receivers();
+        let end = core::cmp::min(from.saturating_add(size), receivers.len());
+        for index in from..end {
+            let receiver = receivers.get_by_index(index + 1);
+            let reserve = self.get_reserve_for_address(&receiver);
+            result.push((receiver, reserve).into());
+        }
+        result
+    }
+}
@@ -0,0 +1,48 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Compliance,
+    Keeper,
+    Admin,
+    Guardian,
+}
+
+/// Owner-granted roles that unlock specific administrative endpoints.
+#[multiversx_sc::module]
+pub trait RolesModule {
+    #[only_owner]
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: ManagedAddress) {
+        self.role_members(role).insert(address);
+    }
+
+    #[only_owner]
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: ManagedAddress) {
+        self.role_members(role).swap_remove(&address);
+    }
+
+    fn require_owner_or_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == self.blockchain().get_owner_address()
+                || self.role_members(role).contains(&caller),
+            "Caller does not have the required role"
+        );
+    }
+
+    fn require_role(&self, role: Role) {
+        require!(
+            self.role_members(role)
+                .contains(&self.blockchain().get_caller()),
+            "Caller does not have the required role"
+        );
+    }
+
+    #[view(getRoleMembers)]
+    #[storage_mapper("roleMembers")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
+}
@@ -87,13 +87,52 @@ where
 {
     pub fn deposit<
         Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
     >(
         self,
         receiver: Arg0,
+        opt_referrer: Arg1,
     ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
         self.wrapped_tx
             .raw_call("deposit")
             .argument(&receiver)
+            .argument(&opt_referrer)
+            .original_result()
+    }
+
+    pub fn deposit_with_memo<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        receiver: Arg0,
+        memo: Arg1,
+        opt_referrer: Arg2,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("depositWithMemo")
+            .argument(&receiver)
+            .argument(&memo)
+            .argument(&opt_referrer)
+            .original_result()
+    }
+
+    /// Moves `amount` from the caller's reserve to `to`, minus the transfer fee. 
+    /// Counts against the caller's withdrawal allowance. 
+    pub fn transfer_reserve<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        to: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("transferReserve")
+            .argument(&to)
+            .argument(&amount)
             .original_result()
     }
 
@@ -119,6 +158,20 @@ where
             .original_result()
     }
 
+    /// Applies a fee proposal that passed the depositors' vote. Callable by anyone. 
+    pub fn execute_fee_proposal<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        proposal_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("executeFeeProposal")
+            .argument(&proposal_id)
+            .original_result()
+    }
+
     pub fn get_fee(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
@@ -128,6 +181,312 @@ where
             .original_result()
     }
 
+    pub fn set_transfer_fee<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        fee: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setTransferFee")
+            .argument(&fee)
+            .original_result()
+    }
+
+    pub fn get_transfer_fee(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTransferFee")
+            .original_result()
+    }
+
+    pub fn get_collected_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getCollectedFees")
+            .original_result()
+    }
+
+    /// Reserves, owner and beneficiary fees, locked escrow tranches, prefunded subscriptions 
+    /// and unredeemed receipts must be covered by the contract balance. 
+    pub fn get_solvency(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, SolvencyInfo<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSolvency")
+            .original_result()
+    }
+
+    pub fn set_deposit_limits<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        min_deposit: Arg0,
+        max_deposit: Arg1,
+        max_reserve_per_receiver: Arg2,
+        max_total_value: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setDepositLimits")
+            .argument(&min_deposit)
+            .argument(&max_deposit)
+            .argument(&max_reserve_per_receiver)
+            .argument(&max_total_value)
+            .original_result()
+    }
+
+    pub fn get_deposit_limits(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, DepositLimits<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDepositLimits")
+            .original_result()
+    }
+
+    pub fn grant_role<
+        Arg0: ProxyArg<Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("grantRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn revoke_role<
+        Arg0: ProxyArg<Role>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        role: Arg0,
+        address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeRole")
+            .argument(&role)
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn role_members<
+        Arg0: ProxyArg<Role>,
+    >(
+        self,
+        role: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRoleMembers")
+            .argument(&role)
+            .original_result()
+    }
+
+    pub fn add_to_blocklist<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addToBlocklist")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn remove_from_blocklist<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFromBlocklist")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn add_to_allowlist<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("addToAllowlist")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn remove_from_allowlist<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("removeFromAllowlist")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    pub fn set_allowlist_only<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enabled: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setAllowlistOnly")
+            .argument(&enabled)
+            .original_result()
+    }
+
+    pub fn is_address_permitted<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isAddressPermitted")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn blocklist(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBlocklist")
+            .original_result()
+    }
+
+    pub fn allowlist(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAllowlist")
+            .original_result()
+    }
+
+    pub fn allowlist_only(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isAllowlistOnly")
+            .original_result()
+    }
+
+    pub fn set_registration_required<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        required: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setRegistrationRequired")
+            .argument(&required)
+            .original_result()
+    }
+
+    pub fn register<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        display_name: Arg0,
+        opt_payout_address: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("register")
+            .argument(&display_name)
+            .argument(&opt_payout_address)
+            .original_result()
+    }
+
+    pub fn unregister(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unregister")
+            .original_result()
+    }
+
+    pub fn get_receiver_info<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ReceiverInfo<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReceiverInfo")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    pub fn registration_required(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isRegistrationRequired")
+            .original_result()
+    }
+
+    pub fn migration_pending(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isMigrationPending")
+            .original_result()
+    }
+
+    /// Timestamp of the address' last own action, or of when its reserve last became non-zero. 
+    pub fn last_activity<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastActivity")
+            .argument(&address)
+            .original_result()
+    }
+
     pub fn get_reserve_for_address<
         Arg0: ProxyArg<ManagedAddress<Env::Api>>,
     >(
@@ -141,12 +500,1382 @@ where
             .original_result()
     }
 
-    pub fn get_collected_fees(
+    pub fn total_reserves(
         self,
     ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
         self.wrapped_tx
             .payment(NotPayable)
-            .raw_call("getCollectedFees")
+            .raw_call("getTotalReserves")
+            .original_result()
+    }
+
+    /// Returns up to `size` records of the receiver's deposit history, oldest first, starting at 
+    /// the zero-based index `from`. Deposits that were already withdrawn are included. 
+    pub fn get_deposit_history<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<usize>,
+        Arg2: ProxyArg<usize>,
+    >(
+        self,
+        receiver: Arg0,
+        from: Arg1,
+        size: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, DepositRecord<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDepositHistory")
+            .argument(&receiver)
+            .argument(&from)
+            .argument(&size)
+            .original_result()
+    }
+
+    pub fn get_receivers_count(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, usize> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReceiversCount")
+            .original_result()
+    }
+
+    /// Returns up to `size` (address, reserve) pairs, starting at the zero-based index `from`. 
+    pub fn get_receivers<
+        Arg0: ProxyArg<usize>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        from: Arg0,
+        size: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, BigUint<Env::Api>>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReceivers")
+            .argument(&from)
+            .argument(&size)
+            .original_result()
+    }
+
+    /// Replaces the beneficiary table. Shares must add up to 10000 basis points, or the 
+    /// table must be empty, in which case all fees go to the owner again. 
+    pub fn set_beneficiaries<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, u32>>>,
+    >(
+        self,
+        beneficiaries: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setBeneficiaries")
+            .argument(&beneficiaries)
             .original_result()
     }
+
+    pub fn claim_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimFees")
+            .original_result()
+    }
+
+    pub fn get_beneficiaries(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, MultiValue2<ManagedAddress<Env::Api>, u32>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getBeneficiaries")
+            .original_result()
+    }
+
+    pub fn accrued_fees<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        beneficiary: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAccruedFees")
+            .argument(&beneficiary)
+            .original_result()
+    }
+
+    pub fn total_accrued_fees(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalAccruedFees")
+            .original_result()
+    }
+
+    pub fn set_referral_share<
+        Arg0: ProxyArg<u32>,
+    >(
+        self,
+        share_bps: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setReferralShare")
+            .argument(&share_bps)
+            .original_result()
+    }
+
+    pub fn referral_share_bps(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferralShare")
+            .original_result()
+    }
+
+    pub fn referral_earnings<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        referrer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferralEarnings")
+            .argument(&referrer)
+            .original_result()
+    }
+
+    pub fn referred_volume<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        referrer: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReferredVolume")
+            .argument(&referrer)
+            .original_result()
+    }
+
+    /// Sends the caller's reserve, up to the withdrawal allowance, to `function` on `target`. 
+    /// If the call fails, the EGLD comes back and is credited to the caller's reserve again, 
+    /// and no longer counts against the withdrawal allowance or the circuit breaker. 
+    pub fn withdraw_and_call<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        target: Arg0,
+        function: Arg1,
+        args: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawAndCall")
+            .argument(&target)
+            .argument(&function)
+            .argument(&args)
+            .original_result()
+    }
+
+    /// Pays out the given receivers in order, skipping frozen ones, those without a reserve 
+    /// and those without withdrawal allowance left. Stops before running out of gas, or when 
+    /// the circuit breaker trips, and returns how many receivers were processed, so the next 
+    /// call can resume from there once the status allows it. 
+    pub fn push_payouts<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        receivers: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValue2<PayoutStatus, usize>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pushPayouts")
+            .argument(&receivers)
+            .original_result()
+    }
+
+    /// A zero period disables sweeping. 
+    pub fn set_dormancy_config<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        period: Arg0,
+        treasury: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setDormancyConfig")
+            .argument(&period)
+            .argument(&treasury)
+            .original_result()
+    }
+
+    pub fn sweep_dormant_reserve<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("sweepDormantReserve")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    /// Seconds left until the receiver's reserve can be swept, if sweeping applies to it. 
+    pub fn get_time_to_expiry<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTimeToExpiry")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    pub fn dormancy_period(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDormancyPeriod")
+            .original_result()
+    }
+
+    pub fn dormancy_treasury(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDormancyTreasury")
+            .original_result()
+    }
+
+    /// Version 0 to 1: indexes the reserves of the given addresses into the receivers set and 
+    /// the running reserve total. Addresses that are already indexed are skipped. 
+    pub fn migrate_reserves<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        addresses: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrateReserves")
+            .argument(&addresses)
+            .original_result()
+    }
+
+    /// Marks the batched step as done and runs the remaining steps, if any. Reserves that 
+    /// were not passed to `migrateReserves` are indexed the next time they are credited or 
+    /// debited. 
+    pub fn complete_migration(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("completeMigration")
+            .original_result()
+    }
+
+    pub fn storage_version(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getStorageVersion")
+            .original_result()
+    }
+
+    /// A zero maximum disables the limit. 
+    pub fn set_withdrawal_rate_limit<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        max_amount: Arg0,
+        window: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setWithdrawalRateLimit")
+            .argument(&max_amount)
+            .argument(&window)
+            .original_result()
+    }
+
+    /// Amount the address can still withdraw in its current window, if a limit is set. 
+    pub fn get_remaining_allowance<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<BigUint<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRemainingAllowance")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn max_withdrawal_per_window(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMaxWithdrawalPerWindow")
+            .original_result()
+    }
+
+    pub fn withdrawal_window_length(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWithdrawalWindowLength")
+            .original_result()
+    }
+
+    /// A zero threshold disables the breaker. 
+    pub fn set_circuit_breaker<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        threshold: Arg0,
+        window: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setCircuitBreaker")
+            .argument(&threshold)
+            .argument(&window)
+            .original_result()
+    }
+
+    pub fn reset_circuit_breaker(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("resetCircuitBreaker")
+            .original_result()
+    }
+
+    pub fn outflow_threshold(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOutflowThreshold")
+            .original_result()
+    }
+
+    pub fn outflow_window_length(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOutflowWindowLength")
+            .original_result()
+    }
+
+    pub fn outflow_window(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, WithdrawalWindow<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOutflowWindow")
+            .original_result()
+    }
+
+    pub fn withdrawals_paused(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("areWithdrawalsPaused")
+            .original_result()
+    }
+
+    pub fn nominate_recovery_address<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        recovery_address: Arg0,
+        waiting_period: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("nominateRecoveryAddress")
+            .argument(&recovery_address)
+            .argument(&waiting_period)
+            .original_result()
+    }
+
+    pub fn cancel_recovery_nomination(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelRecoveryNomination")
+            .original_result()
+    }
+
+    /// Called by the nominated address once the receiver has been inactive for the whole 
+    /// waiting period. Pays the reserve, up to the withdrawal allowance, to the caller. 
+    pub fn claim_as_recovery<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claimAsRecovery")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    pub fn get_recovery_nomination<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<RecoveryNomination<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getRecoveryNomination")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    pub fn approve_operator<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        operator: Arg0,
+        allowance: Arg1,
+        expiry: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("approveOperator")
+            .argument(&operator)
+            .argument(&allowance)
+            .argument(&expiry)
+            .original_result()
+    }
+
+    pub fn revoke_operator<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        operator: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeOperator")
+            .argument(&operator)
+            .original_result()
+    }
+
+    pub fn approve_destination<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        destination: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("approveDestination")
+            .argument(&destination)
+            .original_result()
+    }
+
+    pub fn revoke_destination<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        destination: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("revokeDestination")
+            .argument(&destination)
+            .original_result()
+    }
+
+    /// Called by an approved operator. Pays `amount` from the receiver's reserve to the 
+    /// receiver's payout address, or to a destination the receiver approved. 
+    pub fn withdraw_for<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<OptionalValue<ManagedAddress<Env::Api>>>,
+    >(
+        self,
+        receiver: Arg0,
+        amount: Arg1,
+        opt_destination: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawFor")
+            .argument(&receiver)
+            .argument(&amount)
+            .argument(&opt_destination)
+            .original_result()
+    }
+
+    pub fn get_operator_approval<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+        operator: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<OperatorApproval<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOperatorApproval")
+            .argument(&receiver)
+            .argument(&operator)
+            .original_result()
+    }
+
+    pub fn get_operators<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOperators")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    pub fn approved_destinations<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getApprovedDestinations")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    /// Pays `amount` minus `relayer_tip` to the receiver's payout address and the tip to 
+    /// the caller. The signature must be the receiver's ed25519 signature over the message 
+    /// returned by `getWithdrawalAuthorizationMessage`. 
+    pub fn withdraw_with_signature<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<u64>,
+        Arg5: ProxyArg<ManagedByteArray<Env::Api, 64usize>>,
+    >(
+        self,
+        receiver: Arg0,
+        amount: Arg1,
+        relayer_tip: Arg2,
+        nonce: Arg3,
+        deadline: Arg4,
+        signature: Arg5,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("withdrawWithSignature")
+            .argument(&receiver)
+            .argument(&amount)
+            .argument(&relayer_tip)
+            .argument(&nonce)
+            .argument(&deadline)
+            .argument(&signature)
+            .original_result()
+    }
+
+    pub fn get_withdrawal_authorization_message<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<BigUint<Env::Api>>,
+        Arg3: ProxyArg<u64>,
+        Arg4: ProxyArg<u64>,
+    >(
+        self,
+        receiver: Arg0,
+        amount: Arg1,
+        relayer_tip: Arg2,
+        nonce: Arg3,
+        deadline: Arg4,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWithdrawalAuthorizationMessage")
+            .argument(&receiver)
+            .argument(&amount)
+            .argument(&relayer_tip)
+            .argument(&nonce)
+            .argument(&deadline)
+            .original_result()
+    }
+
+    /// Next nonce the receiver must sign. 
+    pub fn withdrawal_nonce<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        receiver: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getWithdrawalNonce")
+            .argument(&receiver)
+            .original_result()
+    }
+
+    /// The tranche amounts must add up to the payment. No deposit fee is charged. 
+    pub fn create_escrow<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<MultiValueEncoded<Env::Api, MultiValue2<ManagedBuffer<Env::Api>, BigUint<Env::Api>>>>,
+    >(
+        self,
+        receiver: Arg0,
+        arbiter: Arg1,
+        deadline: Arg2,
+        tranches: Arg3,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        self.wrapped_tx
+            .raw_call("createEscrow")
+            .argument(&receiver)
+            .argument(&arbiter)
+            .argument(&deadline)
+            .argument(&tranches)
+            .original_result()
+    }
+
+    /// Called by the sender or the arbiter. Credits the tranche to the receiver's reserve. 
+    pub fn release_tranche<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        escrow_id: Arg0,
+        tranche_index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("releaseTranche")
+            .argument(&escrow_id)
+            .argument(&tranche_index)
+            .original_result()
+    }
+
+    /// Called by the arbiter, or by the sender once the deadline has passed. 
+    /// Refunds the tranche to the sender. 
+    pub fn cancel_tranche<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<usize>,
+    >(
+        self,
+        escrow_id: Arg0,
+        tranche_index: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelTranche")
+            .argument(&escrow_id)
+            .argument(&tranche_index)
+            .original_result()
+    }
+
+    pub fn get_escrow<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        escrow_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<Escrow<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getEscrow")
+            .argument(&escrow_id)
+            .original_result()
+    }
+
+    pub fn last_escrow_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastEscrowId")
+            .original_result()
+    }
+
+    /// Sum of all tranches that are still locked. 
+    pub fn total_escrowed(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalEscrowed")
+            .original_result()
+    }
+
+    pub fn create_subscription<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        merchant: Arg0,
+        amount_per_period: Arg1,
+        period: Arg2,
+    ) -> TxTypedCall<Env, From, To, (), Gas, u64> {
+        self.wrapped_tx
+            .raw_call("createSubscription")
+            .argument(&merchant)
+            .argument(&amount_per_period)
+            .argument(&period)
+            .original_result()
+    }
+
+    pub fn top_up_subscription<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        subscription_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("topUpSubscription")
+            .argument(&subscription_id)
+            .original_result()
+    }
+
+    /// Called by the merchant. Moves `amount` from the subscription balance into the 
+    /// merchant's reserve. 
+    pub fn pull_payment<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        subscription_id: Arg0,
+        amount: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("pullPayment")
+            .argument(&subscription_id)
+            .argument(&amount)
+            .original_result()
+    }
+
+    /// Called by either party. Refunds the remaining balance to the subscriber. 
+    pub fn cancel_subscription<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        subscription_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancelSubscription")
+            .argument(&subscription_id)
+            .original_result()
+    }
+
+    pub fn get_subscription<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        subscription_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<Subscription<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getSubscription")
+            .argument(&subscription_id)
+            .original_result()
+    }
+
+    /// What the merchant can pull right now, limited by both the period allowance and the 
+    /// subscription balance. 
+    pub fn get_available_to_pull<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        subscription_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAvailableToPull")
+            .argument(&subscription_id)
+            .original_result()
+    }
+
+    pub fn last_subscription_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastSubscriptionId")
+            .original_result()
+    }
+
+    pub fn total_subscription_balances(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalSubscriptionBalances")
+            .original_result()
+    }
+
+    /// A zero discount disables the membership discount. 
+    pub fn set_membership_discount<
+        Arg0: ProxyArg<TokenIdentifier<Env::Api>>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        token_id: Arg0,
+        discount_bps: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setMembershipDiscount")
+            .argument(&token_id)
+            .argument(&discount_bps)
+            .original_result()
+    }
+
+    pub fn lock_membership_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("lockMembershipToken")
+            .original_result()
+    }
+
+    pub fn unlock_membership_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("unlockMembershipToken")
+            .original_result()
+    }
+
+    pub fn get_membership_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<TokenIdentifier<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMembershipToken")
+            .original_result()
+    }
+
+    pub fn membership_discount(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getMembershipDiscount")
+            .original_result()
+    }
+
+    pub fn get_locked_membership_token<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        holder: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<EsdtTokenPayment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLockedMembershipToken")
+            .argument(&holder)
+            .original_result()
+    }
+
+    /// A zero voting period disables governance. 
+    pub fn set_governance_config<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u32>,
+    >(
+        self,
+        voting_period: Arg0,
+        quorum_bps: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setGovernanceConfig")
+            .argument(&voting_period)
+            .argument(&quorum_bps)
+            .original_result()
+    }
+
+    pub fn propose_fee<
+        Arg0: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        new_fee: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("proposeFee")
+            .argument(&new_fee)
+            .original_result()
+    }
+
+    pub fn vote<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<bool>,
+    >(
+        self,
+        proposal_id: Arg0,
+        support: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("vote")
+            .argument(&proposal_id)
+            .argument(&support)
+            .original_result()
+    }
+
+    /// The voter's volume from deposits made before the proposal was created. 
+    pub fn get_voting_weight<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        proposal_id: Arg0,
+        voter: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getVotingWeight")
+            .argument(&proposal_id)
+            .argument(&voter)
+            .original_result()
+    }
+
+    pub fn get_proposal<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        proposal_id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<FeeProposal<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getProposal")
+            .argument(&proposal_id)
+            .original_result()
+    }
+
+    pub fn last_proposal_id(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getLastProposalId")
+            .original_result()
+    }
+
+    pub fn has_voted<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        proposal_id: Arg0,
+        voter: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("hasVoted")
+            .argument(&proposal_id)
+            .argument(&voter)
+            .original_result()
+    }
+
+    pub fn voting_period(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getVotingPeriod")
+            .original_result()
+    }
+
+    pub fn quorum_bps(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u32> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getQuorum")
+            .original_result()
+    }
+
+    pub fn deposited_volume<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        depositor: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getDepositedVolume")
+            .argument(&depositor)
+            .original_result()
+    }
+
+    pub fn total_deposited_volume(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getTotalDepositedVolume")
+            .original_result()
+    }
+
+    /// A zero threshold disables forwarding. Treasury contracts must be payable by 
+    /// contracts, so that forwarding cannot make deposits fail. 
+    pub fn set_fee_treasury<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<BigUint<Env::Api>>,
+    >(
+        self,
+        treasury: Arg0,
+        threshold: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setFeeTreasury")
+            .argument(&treasury)
+            .argument(&threshold)
+            .original_result()
+    }
+
+    pub fn fee_treasury(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedAddress<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeTreasury")
+            .original_result()
+    }
+
+    pub fn fee_forward_threshold(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getFeeForwardThreshold")
+            .original_result()
+    }
+
+    pub fn get_address_stats<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        address: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, AddressStats<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getAddressStats")
+            .argument(&address)
+            .original_result()
+    }
+
+    pub fn get_global_stats(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, GlobalStats<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getGlobalStats")
+            .original_result()
+    }
+
+    /// Issues the receipt collection with create and burn roles for this contract. 
+    /// The payment covers the issue cost and is returned if issuing fails. 
+    pub fn issue_receipt_token<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        token_name: Arg0,
+        token_ticker: Arg1,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("issueReceiptToken")
+            .argument(&token_name)
+            .argument(&token_ticker)
+            .original_result()
+    }
+
+    pub fn set_receipt_mode<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        enabled: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("setReceiptMode")
+            .argument(&enabled)
+            .original_result()
+    }
+
+    /// Burns the receipt sent with the call and credits its amount to the caller's reserve. 
+    pub fn redeem_receipt(
+        self,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("redeemReceipt")
+            .original_result()
+    }
+
+    pub fn receipt_token(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, TokenIdentifier<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getReceiptToken")
+            .original_result()
+    }
+
+    pub fn receipt_mode(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("isReceiptModeEnabled")
+            .original_result()
+    }
+
+    /// Sum of the amounts of all receipts not yet redeemed. 
+    pub fn outstanding_receipts(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, BigUint<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("getOutstandingReceipts")
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct SolvencyInfo<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub total_reserves: BigUint<Api>,
+    pub collected_fees: BigUint<Api>,
+    pub accrued_fees: BigUint<Api>,
+    pub total_escrowed: BigUint<Api>,
+    pub subscription_balances: BigUint<Api>,
+    pub outstanding_receipts: BigUint<Api>,
+    pub balance: BigUint<Api>,
+    pub is_solvent: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct DepositLimits<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub min_deposit: BigUint<Api>,
+    pub max_deposit: BigUint<Api>,
+    pub max_reserve_per_receiver: BigUint<Api>,
+    pub max_total_value: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Compliance,
+    Keeper,
+    Admin,
+    Guardian,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ReceiverInfo<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub display_name: ManagedBuffer<Api>,
+    pub payout_address: ManagedAddress<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct DepositRecord<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub sender: ManagedAddress<Api>,
+    pub amount: BigUint<Api>,
+    pub timestamp: u64,
+    pub memo: ManagedBuffer<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PayoutStatus {
+    Completed,
+    InterruptedBeforeOutOfGas,
+    CircuitBreakerTripped,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct WithdrawalWindow<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub start: u64,
+    pub withdrawn: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct RecoveryNomination<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub recovery_address: ManagedAddress<Api>,
+    pub waiting_period: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct OperatorApproval<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub allowance: BigUint<Api>,
+    pub expiry: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Escrow<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub sender: ManagedAddress<Api>,
+    pub receiver: ManagedAddress<Api>,
+    pub arbiter: ManagedAddress<Api>,
+    pub deadline: u64,
+    pub tranches: ManagedVec<Api, Tranche<Api>>,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug,
+)]
+pub struct Tranche<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub name: ManagedBuffer<Api>,
+    pub amount: BigUint<Api>,
+    pub status: TrancheStatus,
+}
+
+#[type_abi]
+#[derive(
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    ManagedVecItem,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+)]
+pub enum TrancheStatus {
+    Locked,
+    Released,
+    Cancelled,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Subscription<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub subscriber: ManagedAddress<Api>,
+    pub merchant: ManagedAddress<Api>,
+    pub amount_per_period: BigUint<Api>,
+    pub period: u64,
+    pub start: u64,
+    pub balance: BigUint<Api>,
+    pub current_period: u64,
+    pub pulled_in_period: BigUint<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct FeeProposal<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub proposer: ManagedAddress<Api>,
+    pub new_fee: BigUint<Api>,
+    pub end: u64,
+    pub total_volume: BigUint<Api>,
+    pub quorum_bps: u32,
+    pub votes_for: BigUint<Api>,
+    pub votes_against: BigUint<Api>,
+    pub executed: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct AddressStats<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub deposited: BigUint<Api>,
+    pub received: BigUint<Api>,
+    pub fees_paid: BigUint<Api>,
+    pub withdrawal_count: u64,
+    pub last_activity: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct GlobalStats<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub deposit_count: u64,
+    pub total_deposited: BigUint<Api>,
+    pub total_fees: BigUint<Api>,
+    pub withdrawal_count: u64,
+    pub total_withdrawn: BigUint<Api>,
 }
@@ -0,0 +1,138 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::{
+    circuit_breaker, compliance, events, rate_limit, registration, reserves, roles, statistics,
+};
+
+/// What the receiver signs, top-encoded, to authorize a relayed withdrawal.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct WithdrawalAuthorization<M: ManagedTypeApi> {
+    pub contract: ManagedAddress<M>,
+    pub receiver: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub relayer_tip: BigUint<M>,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+/// Withdrawals authorized off-chain by the receiver and submitted by a relayer paying the gas.
+#[multiversx_sc::module]
+pub trait SignedWithdrawalsModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + events::EventsModule
+    + rate_limit::RateLimitModule
+    + circuit_breaker::CircuitBreakerModule
+    + statistics::StatisticsModule
+{
+    /// Pays `amount` minus `relayer_tip` to the receiver's payout address and the tip to
+    /// the caller. The signature must be the receiver's ed25519 signature over the message
+    /// returned by `getWithdrawalAuthorizationMessage`.
+    #[endpoint(withdrawWithSignature)]
+    fn withdraw_with_signature(
+        &self,
+        receiver: ManagedAddress,
+        amount: BigUint,
+        relayer_tip: BigUint,
+        nonce: u64,
+        deadline: u64,
+        signature: ManagedByteArray<64>,
+    ) {
+        let relayer = self.blockchain().get_caller();
+        require!(
+            self.blockchain().get_block_timestamp() <= deadline,
+            "Authorization expired"
+        );
+        require!(
+            nonce == self.withdrawal_nonce(&receiver).get(),
+            "Invalid nonce"
+        );
+        require!(amount > 0, "Amount must be greater than zero");
+        require!(relayer_tip <= amount, "Tip exceeds amount");
+
+        let message = self.get_withdrawal_authorization_message(
+            receiver.clone(),
+            amount.clone(),
+            relayer_tip.clone(),
+            nonce,
+            deadline,
+        );
+        self.crypto().verify_ed25519(
+            receiver.as_managed_buffer(),
+            &message,
+            signature.as_managed_buffer(),
+        );
+
+        require!(
+            self.is_address_permitted(&receiver) && self.is_address_permitted(&relayer),
+            "Caller is not permitted"
+        );
+        let payout_address = self.get_payout_address(&receiver);
+        require!(
+            self.is_address_permitted(&payout_address),
+            "Payout address is not permitted"
+        );
+        self.require_withdrawals_not_paused();
+        require!(
+            amount <= self.get_reserve_for_address(&receiver),
+            "Insufficient reserve"
+        );
+        require!(
+            amount <= self.get_allowed_withdrawal(&receiver, &amount),
+            "Withdrawal limit reached"
+        );
+        // a tripped breaker leaves the nonce unused, so the authorization can be relayed
+        // again once withdrawals resume
+        if !self.record_outflow(&amount) {
+            return;
+        }
+        self.withdrawal_nonce(&receiver).set(nonce + 1);
+        self.record_withdrawal(&receiver, &amount);
+        self.debit_reserve(&receiver, &amount);
+        self.record_withdrawal_stats(&receiver, &amount);
+        self.touch_activity(&receiver);
+
+        let payout = &amount - &relayer_tip;
+        if payout > 0 {
+            self.tx().to(&payout_address).egld(&payout).transfer();
+        }
+        if relayer_tip > 0 {
+            self.tx().to(&relayer).egld(&relayer_tip).transfer();
+        }
+        self.signed_withdrawal_event(&receiver, &relayer, &amount, &relayer_tip);
+    }
+
+    #[view(getWithdrawalAuthorizationMessage)]
+    fn get_withdrawal_authorization_message(
+        &self,
+        receiver: ManagedAddress,
+        amount: BigUint,
+        relayer_tip: BigUint,
+        nonce: u64,
+        deadline: u64,
+    ) -> ManagedBuffer {
+        let authorization = WithdrawalAuthorization {
+            contract: self.blockchain().get_sc_address(),
+            receiver,
+            amount,
+            relayer_tip,
+            nonce,
+            deadline,
+        };
+
+        let mut message = ManagedBuffer::new();
+        if authorization.top_encode(&mut message).is_err() {
+            sc_panic!("Cannot encode withdrawal authorization");
+        }
+        message
+    }
+
+    /// Next nonce the receiver must sign.
+    #[view(getWithdrawalNonce)]
+    #[storage_mapper("withdrawalNonce")]
+    fn withdrawal_nonce(&self, receiver: &ManagedAddress) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,111 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+/// Lifetime totals for one address. `deposited` and `fees_paid` count it as a sender,
+/// `received` counts it as a receiver.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct AddressStats<M: ManagedTypeApi> {
+    pub deposited: BigUint<M>,
+    pub received: BigUint<M>,
+    pub fees_paid: BigUint<M>,
+    pub withdrawal_count: u64,
+    pub last_activity: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct GlobalStats<M: ManagedTypeApi> {
+    pub deposit_count: u64,
+    pub total_deposited: BigUint<M>,
+    pub total_fees: BigUint<M>,
+    pub withdrawal_count: u64,
+    pub total_withdrawn: BigUint<M>,
+}
+
+/// Lifetime counters for dashboards. Deposits count as `deposited`. `received` also counts
+/// reserve transfers, released escrow tranches and subscription pulls. Every path that pays a
+/// reserve out of the contract counts as a withdrawal, while referral rewards, receipt
+/// redemptions and refunds of escrows or subscriptions are not counted.
+#[multiversx_sc::module]
+pub trait StatisticsModule {
+    fn record_deposit_stats(
+        &self,
+        sender: &ManagedAddress,
+        receiver: &ManagedAddress,
+        payment_amount: &BigUint,
+        received: &BigUint,
+    ) {
+        let now = self.blockchain().get_block_timestamp();
+        let fee = &(payment_amount - received);
+        let mut sender_stats = self.get_address_stats(sender.clone());
+        sender_stats.deposited += payment_amount;
+        sender_stats.fees_paid += fee;
+        sender_stats.last_activity = now;
+        self.address_stats(sender).set(sender_stats);
+
+        self.record_received_stats(receiver, received);
+
+        let mut global_stats = self.get_global_stats();
+        global_stats.deposit_count += 1;
+        global_stats.total_deposited += payment_amount;
+        global_stats.total_fees += fee;
+        self.global_stats().set(global_stats);
+    }
+
+    /// For value credited to a reserve from another reserve, an escrow or a subscription.
+    fn record_received_stats(&self, receiver: &ManagedAddress, amount: &BigUint) {
+        let mut receiver_stats = self.get_address_stats(receiver.clone());
+        receiver_stats.received += amount;
+        receiver_stats.last_activity = self.blockchain().get_block_timestamp();
+        self.address_stats(receiver).set(receiver_stats);
+    }
+
+    /// For value that leaves the contract out of a reserve.
+    fn record_withdrawal_stats(&self, receiver: &ManagedAddress, amount: &BigUint) {
+        let mut receiver_stats = self.get_address_stats(receiver.clone());
+        receiver_stats.withdrawal_count += 1;
+        receiver_stats.last_activity = self.blockchain().get_block_timestamp();
+        self.address_stats(receiver).set(receiver_stats);
+
+        let mut global_stats = self.get_global_stats();
+        global_stats.withdrawal_count += 1;
+        global_stats.total_withdrawn += amount;
+        self.global_stats().set(global_stats);
+    }
+
+    #[view(getAddressStats)]
+    fn get_address_stats(&self, address: ManagedAddress) -> AddressStats<Self::Api> {
+        if self.address_stats(&address).is_empty() {
+            return AddressStats {
+                deposited: BigUint::zero(),
+                received: BigUint::zero(),
+                fees_paid: BigUint::zero(),
+                withdrawal_count: 0,
+                last_activity: 0,
+            };
+        }
+        self.address_stats(&address).get()
+    }
+
+    #[view(getGlobalStats)]
+    fn get_global_stats(&self) -> GlobalStats<Self::Api> {
+        if self.global_stats().is_empty() {
+            return GlobalStats {
+                deposit_count: 0,
+                total_deposited: BigUint::zero(),
+                total_fees: BigUint::zero(),
+                withdrawal_count: 0,
+                total_withdrawn: BigUint::zero(),
+            };
+        }
+        self.global_stats().get()
+    }
+
+    #[storage_mapper("addressStats")]
+    fn address_stats(&self, address: &ManagedAddress)
+        -> SingleValueMapper<AddressStats<Self::Api>>;
+
+    #[storage_mapper("globalStats")]
+    fn global_stats(&self) -> SingleValueMapper<GlobalStats<Self::Api>>;
+}
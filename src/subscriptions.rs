@@ -0,0 +1,234 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::{compliance, events, limits, registration, reserves, roles, statistics};
+
+/// Periods are counted from `start`, so period `n` covers
+/// `[start + n * period, start + (n + 1) * period)`.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct Subscription<M: ManagedTypeApi> {
+    pub subscriber: ManagedAddress<M>,
+    pub merchant: ManagedAddress<M>,
+    pub amount_per_period: BigUint<M>,
+    pub period: u64,
+    pub start: u64,
+    pub balance: BigUint<M>,
+    pub current_period: u64,
+    pub pulled_in_period: BigUint<M>,
+}
+
+/// Pull payments: subscribers prefund a subscription, and the merchant pulls up to the
+/// agreed amount per period into its reserve.
+#[multiversx_sc::module]
+pub trait SubscriptionsModule:
+    reserves::ReservesModule
+    + roles::RolesModule
+    + compliance::ComplianceModule
+    + registration::RegistrationModule
+    + limits::LimitsModule
+    + events::EventsModule
+    + statistics::StatisticsModule
+{
+    #[payable("EGLD")]
+    #[endpoint(createSubscription)]
+    fn create_subscription(
+        &self,
+        merchant: ManagedAddress,
+        amount_per_period: BigUint,
+        period: u64,
+    ) -> u64 {
+        let subscriber = self.blockchain().get_caller();
+        let payment_amount = self.call_value().egld_value().clone_value();
+        require!(payment_amount > 0, "Payment must be greater than zero");
+        require!(
+            self.is_address_permitted(&subscriber),
+            "Sender is not permitted"
+        );
+        require!(
+            self.is_address_permitted(&merchant),
+            "Receiver is not permitted"
+        );
+        self.require_receiver_accepts_deposits(&merchant);
+        require!(subscriber != merchant, "Cannot subscribe to yourself");
+        require!(
+            amount_per_period > 0,
+            "Amount per period must be greater than zero"
+        );
+        require!(period > 0, "Period must be greater than zero");
+        // the reserve cap is checked again on every pull
+        self.require_deposit_within_limits(
+            &payment_amount,
+            &self.get_reserve_for_address(&merchant),
+        );
+
+        let subscription_id = self.last_subscription_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        self.total_subscription_balances()
+            .update(|total| *total += &payment_amount);
+        self.subscriptions(subscription_id).set(Subscription {
+            subscriber,
+            merchant,
+            amount_per_period,
+            period,
+            start: self.blockchain().get_block_timestamp(),
+            balance: payment_amount,
+            current_period: 0,
+            pulled_in_period: BigUint::zero(),
+        });
+
+        subscription_id
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(topUpSubscription)]
+    fn top_up_subscription(&self, subscription_id: u64) {
+        let payment_amount = self.call_value().egld_value().clone_value();
+        require!(payment_amount > 0, "Payment must be greater than zero");
+        let mut subscription = self.get_existing_subscription(subscription_id);
+        require!(
+            self.blockchain().get_caller() == subscription.subscriber,
+            "Only the subscriber can top up"
+        );
+        require!(
+            self.is_address_permitted(&subscription.subscriber),
+            "Sender is not permitted"
+        );
+        self.require_deposit_within_limits(
+            &payment_amount,
+            &self.get_reserve_for_address(&subscription.merchant),
+        );
+
+        subscription.balance += &payment_amount;
+        self.subscriptions(subscription_id).set(&subscription);
+        self.total_subscription_balances()
+            .update(|total| *total += &payment_amount);
+    }
+
+    /// Called by the merchant. Moves `amount` from the subscription balance into the
+    /// merchant's reserve.
+    #[endpoint(pullPayment)]
+    fn pull_payment(&self, subscription_id: u64, amount: BigUint) {
+        let mut subscription = self.get_existing_subscription(subscription_id);
+        require!(
+            self.blockchain().get_caller() == subscription.merchant,
+            "Only the merchant can pull"
+        );
+        require!(
+            self.is_address_permitted(&subscription.merchant),
+            "Caller is not permitted"
+        );
+        require!(amount > 0, "Amount must be greater than zero");
+
+        self.roll_period(&mut subscription);
+        require!(
+            &subscription.pulled_in_period + &amount <= subscription.amount_per_period,
+            "Amount exceeds period allowance"
+        );
+        require!(
+            amount <= subscription.balance,
+            "Insufficient subscription balance"
+        );
+        self.require_reserve_within_cap(
+            &(self.get_reserve_for_address(&subscription.merchant) + &amount),
+        );
+
+        subscription.pulled_in_period += &amount;
+        subscription.balance -= &amount;
+        self.subscriptions(subscription_id).set(&subscription);
+        self.total_subscription_balances()
+            .update(|total| *total -= &amount);
+
+        self.credit_reserve(&subscription.merchant, &amount);
+        self.record_received_stats(&subscription.merchant, &amount);
+        self.subscription_pull_event(
+            subscription_id,
+            &subscription.subscriber,
+            &subscription.merchant,
+            subscription.current_period,
+            &amount,
+        );
+    }
+
+    /// Called by either party. Refunds the remaining balance to the subscriber.
+    #[endpoint(cancelSubscription)]
+    fn cancel_subscription(&self, subscription_id: u64) {
+        let caller = self.blockchain().get_caller();
+        let subscription = self.get_existing_subscription(subscription_id);
+        require!(
+            caller == subscription.subscriber || caller == subscription.merchant,
+            "Only the subscriber or the merchant can cancel"
+        );
+        require!(
+            self.is_address_permitted(&subscription.subscriber),
+            "Subscriber is not permitted"
+        );
+
+        self.subscriptions(subscription_id).clear();
+        self.total_subscription_balances()
+            .update(|total| *total -= &subscription.balance);
+        if subscription.balance > 0 {
+            self.tx()
+                .to(&subscription.subscriber)
+                .egld(&subscription.balance)
+                .transfer();
+        }
+        self.subscription_cancelled_event(
+            subscription_id,
+            &subscription.subscriber,
+            &subscription.merchant,
+            &subscription.balance,
+        );
+    }
+
+    fn get_existing_subscription(&self, subscription_id: u64) -> Subscription<Self::Api> {
+        require!(
+            !self.subscriptions(subscription_id).is_empty(),
+            "Subscription not found"
+        );
+        self.subscriptions(subscription_id).get()
+    }
+
+    fn roll_period(&self, subscription: &mut Subscription<Self::Api>) {
+        let now = self.blockchain().get_block_timestamp();
+        let period = (now - subscription.start) / subscription.period;
+        if period != subscription.current_period {
+            subscription.current_period = period;
+            subscription.pulled_in_period = BigUint::zero();
+        }
+    }
+
+    #[view(getSubscription)]
+    fn get_subscription(&self, subscription_id: u64) -> OptionalValue<Subscription<Self::Api>> {
+        if self.subscriptions(subscription_id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.subscriptions(subscription_id).get())
+    }
+
+    /// What the merchant can pull right now, limited by both the period allowance and the
+    /// subscription balance.
+    #[view(getAvailableToPull)]
+    fn get_available_to_pull(&self, subscription_id: u64) -> BigUint {
+        if self.subscriptions(subscription_id).is_empty() {
+            return BigUint::zero();
+        }
+        let mut subscription = self.subscriptions(subscription_id).get();
+        self.roll_period(&mut subscription);
+        let allowance = &subscription.amount_per_period - &subscription.pulled_in_period;
+        core::cmp::min(allowance, subscription.balance)
+    }
+
+    #[storage_mapper("subscriptions")]
+    fn subscriptions(&self, subscription_id: u64) -> SingleValueMapper<Subscription<Self::Api>>;
+
+    #[view(getLastSubscriptionId)]
+    #[storage_mapper("lastSubscriptionId")]
+    fn last_subscription_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getTotalSubscriptionBalances)]
+    #[storage_mapper("totalSubscriptionBalances")]
+    fn total_subscription_balances(&self) -> SingleValueMapper<BigUint>;
+}
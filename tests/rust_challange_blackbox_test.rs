@@ -7,6 +7,7 @@ const ADDRESS1: TestAddress = TestAddress::new("address1");
 const ADDRESS2: TestAddress = TestAddress::new("address2");
 const RECEIVER_ADDRESS: TestAddress = TestAddress::new("receiver");
 const CONTRACT_ADDRESS: TestSCAddress = TestSCAddress::new("rust-challenge");
+const NO_REFERRER: OptionalValue<ManagedAddress<StaticApi>> = OptionalValue::None;
 const CODE_PATH: MxscPath = MxscPath::new("output/rust_challenge.mxsc.json");
 
 fn world() -> ScenarioWorld {
@@ -50,7 +51,7 @@ impl RustChallengeTest {
             .from(from_address)
             .to(CONTRACT_ADDRESS)
             .typed(rust_challenge_proxy::RustChallengeProxy)
-            .deposit(RECEIVER_ADDRESS)
+            .deposit(RECEIVER_ADDRESS, NO_REFERRER)
             .egld(wrong_deposit)
             .with_result(ExpectError(4, "Payments must be greater than fee"))
             .run();
@@ -62,9 +63,132 @@ impl RustChallengeTest {
             .from(sender)
             .to(CONTRACT_ADDRESS)
             .typed(rust_challenge_proxy::RustChallengeProxy)
-            .deposit(receiver)
+            .deposit(receiver, NO_REFERRER)
             .egld(value)
             .run();
+        self.check_solvency();
+    }
+
+    fn deposit_with_referrer(
+        &mut self,
+        sender: TestAddress,
+        receiver: TestAddress,
+        referrer: TestAddress,
+        value: u64,
+    ) {
+        self.world
+            .tx()
+            .from(sender)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .deposit(receiver, OptionalValue::Some(referrer))
+            .egld(value)
+            .run();
+        self.check_solvency();
+    }
+
+    fn deposit_expect_error(
+        &mut self,
+        sender: TestAddress,
+        receiver: TestAddress,
+        value: u64,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(sender)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .deposit(receiver, NO_REFERRER)
+            .egld(value)
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn set_deposit_limits(
+        &mut self,
+        min_deposit: u64,
+        max_deposit: u64,
+        max_reserve_per_receiver: u64,
+        max_total_value: u64,
+    ) {
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .set_deposit_limits(
+                min_deposit,
+                max_deposit,
+                max_reserve_per_receiver,
+                max_total_value,
+            )
+            .run();
+    }
+
+    fn grant_role(&mut self, role: rust_challenge_proxy::Role, address: TestAddress) {
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .grant_role(role, address)
+            .run();
+    }
+
+    fn block(&mut self, from: TestAddress, address: TestAddress) {
+        self.world
+            .tx()
+            .from(from)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .add_to_blocklist(MultiValueVec::from(vec![address.to_address()]))
+            .run();
+    }
+
+    fn unblock(&mut self, from: TestAddress, address: TestAddress) {
+        self.world
+            .tx()
+            .from(from)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .remove_from_blocklist(MultiValueVec::from(vec![address.to_address()]))
+            .run();
+    }
+
+    fn set_beneficiaries(&mut self, beneficiaries: Vec<(TestAddress, u32)>) {
+        let beneficiaries = beneficiaries
+            .into_iter()
+            .map(|(address, share)| (address.to_address(), share).into())
+            .collect::<Vec<MultiValue2<Address, u32>>>();
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .set_beneficiaries(MultiValueVec::from(beneficiaries))
+            .run();
+    }
+
+    fn claim_fees(&mut self, beneficiary: TestAddress) {
+        self.world
+            .tx()
+            .from(beneficiary)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .claim_fees()
+            .run();
+        self.check_solvency();
+    }
+
+    fn query_accrued_fees(&mut self, beneficiary: TestAddress) -> RustBigUint {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .accrued_fees(beneficiary)
+            .returns(ReturnsResultUnmanaged)
+            .run()
     }
 
     fn withdraw(&mut self, sender: TestAddress) {
@@ -75,6 +199,18 @@ impl RustChallengeTest {
             .typed(rust_challenge_proxy::RustChallengeProxy)
             .withdraw()
             .run();
+        self.check_solvency();
+    }
+
+    fn withdraw_expect_error(&mut self, sender: TestAddress, message: &str) {
+        self.world
+            .tx()
+            .from(sender)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .withdraw()
+            .with_result(ExpectError(4, message))
+            .run();
     }
 
     fn query_collected_fees(&mut self) -> RustBigUint {
@@ -97,204 +233,2978 @@ impl RustChallengeTest {
             .run()
     }
 
+    fn query_receivers(&mut self, from: usize, size: usize) -> Vec<(Address, RustBigUint)> {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_receivers(from, size)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .into_vec()
+            .into_iter()
+            .map(|pair| pair.into_tuple())
+            .collect()
+    }
+
+    fn query_receivers_count(&mut self) -> usize {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_receivers_count()
+            .returns(ReturnsResult)
+            .run()
+    }
+
+    fn check_solvency(&mut self) {
+        let solvency = self
+            .world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_solvency()
+            .returns(ReturnsResult)
+            .run();
+        assert!(solvency.is_solvent);
+        assert!(
+            solvency.total_reserves.clone()
+                + solvency.collected_fees.clone()
+                + solvency.accrued_fees.clone()
+                + solvency.total_escrowed.clone()
+                + solvency.subscription_balances.clone()
+                + solvency.outstanding_receipts.clone()
+                <= solvency.balance
+        );
+
+        let count = self.query_receivers_count();
+        let reserves_sum = self
+            .query_receivers(0, count)
+            .into_iter()
+            .fold(RustBigUint::ZERO, |sum, (_, reserve)| sum + reserve);
+        assert_eq!(
+            reserves_sum,
+            RustBigUint::from_bytes_be(solvency.total_reserves.to_bytes_be().as_slice())
+        );
+    }
+
+    fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.world.current_block().block_timestamp(timestamp);
+    }
+
+    fn query_remaining_allowance(&mut self, address: TestAddress) -> Option<RustBigUint> {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_remaining_allowance(address)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .into_option()
+    }
+
+    fn nominate_recovery_address(
+        &mut self,
+        receiver: TestAddress,
+        recovery_address: TestAddress,
+        waiting_period: u64,
+    ) {
+        self.world
+            .tx()
+            .from(receiver)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .nominate_recovery_address(recovery_address, waiting_period)
+            .run();
+    }
+
+    fn claim_as_recovery(&mut self, caller: TestAddress, receiver: TestAddress) {
+        self.world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .claim_as_recovery(receiver)
+            .run();
+        self.check_solvency();
+    }
+
+    fn claim_as_recovery_expect_error(
+        &mut self,
+        caller: TestAddress,
+        receiver: TestAddress,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .claim_as_recovery(receiver)
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn approve_operator(
+        &mut self,
+        receiver: TestAddress,
+        operator: TestAddress,
+        allowance: u64,
+        expiry: u64,
+    ) {
+        self.world
+            .tx()
+            .from(receiver)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .approve_operator(operator, allowance, expiry)
+            .run();
+    }
+
+    fn withdraw_for(
+        &mut self,
+        operator: TestAddress,
+        receiver: TestAddress,
+        amount: u64,
+        destination: Option<TestAddress>,
+    ) {
+        self.world
+            .tx()
+            .from(operator)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .withdraw_for(receiver, amount, OptionalValue::from(destination))
+            .run();
+        self.check_solvency();
+    }
+
+    fn withdraw_for_expect_error(
+        &mut self,
+        operator: TestAddress,
+        receiver: TestAddress,
+        amount: u64,
+        destination: Option<TestAddress>,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(operator)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .withdraw_for(receiver, amount, OptionalValue::from(destination))
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn withdraw_and_call(
+        &mut self,
+        receiver: TestAddress,
+        target: TestSCAddress,
+        function: &[u8],
+        args: Vec<Vec<u8>>,
+    ) {
+        self.world
+            .tx()
+            .from(receiver)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .withdraw_and_call(target, function, MultiValueVec::from(args))
+            .run();
+        self.check_solvency();
+    }
+
+    fn push_payouts(
+        &mut self,
+        keeper: TestAddress,
+        gas: u64,
+        receivers: &[TestAddress],
+    ) -> (rust_challenge_proxy::PayoutStatus, usize) {
+        let receivers = receivers
+            .iter()
+            .map(|receiver| receiver.to_address())
+            .collect::<Vec<_>>();
+        let result = self
+            .world
+            .tx()
+            .from(keeper)
+            .to(CONTRACT_ADDRESS)
+            .gas(gas)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .push_payouts(MultiValueVec::from(receivers))
+            .returns(ReturnsResult)
+            .run()
+            .into_tuple();
+        self.check_solvency();
+        result
+    }
+
+    fn push_payouts_expect_error(
+        &mut self,
+        keeper: TestAddress,
+        receivers: &[TestAddress],
+        message: &str,
+    ) {
+        let receivers = receivers
+            .iter()
+            .map(|receiver| receiver.to_address())
+            .collect::<Vec<_>>();
+        self.world
+            .tx()
+            .from(keeper)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .push_payouts(MultiValueVec::from(receivers))
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn sweep_dormant_reserve(&mut self, caller: TestAddress, receiver: TestAddress) {
+        self.world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .sweep_dormant_reserve(receiver)
+            .run();
+        self.check_solvency();
+    }
+
+    fn sweep_dormant_reserve_expect_error(
+        &mut self,
+        caller: TestAddress,
+        receiver: TestAddress,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .sweep_dormant_reserve(receiver)
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn redeem_receipt(
+        &mut self,
+        holder: TestAddress,
+        receipt_token: &TokenIdentifier<StaticApi>,
+        nonce: u64,
+    ) {
+        self.world
+            .tx()
+            .from(holder)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .redeem_receipt()
+            .single_esdt(receipt_token, nonce, &BigUint::from(1u32))
+            .run();
+        self.check_solvency();
+    }
+
+    fn redeem_receipt_expect_error(
+        &mut self,
+        holder: TestAddress,
+        receipt_token: &TokenIdentifier<StaticApi>,
+        nonce: u64,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(holder)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .redeem_receipt()
+            .single_esdt(receipt_token, nonce, &BigUint::from(1u32))
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
     fn check_account(&mut self, address: TestAddress, balance: u64) {
         self.world.check_account(address).balance(balance);
     }
-}
 
-#[test]
-fn test_deploy() {
-    let mut state = RustChallengeTest::new();
-    let address = state.deploy(1u32);
-    assert_eq!(address, CONTRACT_ADDRESS);
-}
+    fn signer(&mut self) -> (ed25519_dalek::SigningKey, Address) {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&SIGNER_SECRET_KEY);
+        let address = Address::from(signing_key.verifying_key().to_bytes());
+        self.world.account(&address).nonce(1);
+        (signing_key, address)
+    }
 
-#[test]
-fn test_deposit_fail() {
-    let mut state = RustChallengeTest::new();
-    state.deploy(1u32);
+    fn sign_withdrawal(
+        &mut self,
+        signing_key: &ed25519_dalek::SigningKey,
+        withdrawal: &SignedWithdrawal,
+    ) -> ManagedByteArray<StaticApi, 64> {
+        use ed25519_dalek::Signer;
+
+        let message = self
+            .world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_withdrawal_authorization_message(
+                &withdrawal.receiver,
+                withdrawal.amount,
+                withdrawal.relayer_tip,
+                withdrawal.nonce,
+                withdrawal.deadline,
+            )
+            .returns(ReturnsResult)
+            .run();
+        let signature = signing_key.sign(&message.to_vec());
+        ManagedByteArray::new_from_bytes(&signature.to_bytes())
+    }
+
+    fn withdraw_with_signature(
+        &mut self,
+        relayer: TestAddress,
+        withdrawal: &SignedWithdrawal,
+        signature: &ManagedByteArray<StaticApi, 64>,
+        expected_error: Option<&str>,
+    ) {
+        let tx = self
+            .world
+            .tx()
+            .from(relayer)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .withdraw_with_signature(
+                &withdrawal.receiver,
+                withdrawal.amount,
+                withdrawal.relayer_tip,
+                withdrawal.nonce,
+                withdrawal.deadline,
+                signature,
+            );
+        match expected_error {
+            Some(message) => tx.with_result(ExpectError(4, message)).run(),
+            None => {
+                tx.run();
+                self.check_solvency();
+            }
+        }
+    }
+
+    fn create_escrow(
+        &mut self,
+        sender: TestAddress,
+        receiver: TestAddress,
+        arbiter: TestAddress,
+        deadline: u64,
+        tranches: Vec<(&str, u64)>,
+    ) -> u64 {
+        let mut args = MultiValueEncoded::<StaticApi, _>::new();
+        let mut total = 0u64;
+        for (name, amount) in tranches {
+            args.push(MultiValue2::from((
+                ManagedBuffer::from(name),
+                BigUint::from(amount),
+            )));
+            total += amount;
+        }
+        let escrow_id = self
+            .world
+            .tx()
+            .from(sender)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .create_escrow(receiver, arbiter, deadline, args)
+            .egld(total)
+            .returns(ReturnsResult)
+            .run();
+        self.check_solvency();
+        escrow_id
+    }
+
+    fn release_tranche(&mut self, caller: TestAddress, escrow_id: u64, tranche_index: usize) {
+        self.world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .release_tranche(escrow_id, tranche_index)
+            .run();
+        self.check_solvency();
+    }
+
+    fn cancel_tranche(&mut self, caller: TestAddress, escrow_id: u64, tranche_index: usize) {
+        self.world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .cancel_tranche(escrow_id, tranche_index)
+            .run();
+        self.check_solvency();
+    }
+
+    fn escrow_expect_error(
+        &mut self,
+        caller: TestAddress,
+        escrow_id: u64,
+        tranche_index: usize,
+        release: bool,
+        message: &str,
+    ) {
+        let tx = self
+            .world
+            .tx()
+            .from(caller)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy);
+        let tx = if release {
+            tx.release_tranche(escrow_id, tranche_index)
+        } else {
+            tx.cancel_tranche(escrow_id, tranche_index)
+        };
+        tx.with_result(ExpectError(4, message)).run();
+    }
+
+    fn query_tranche_statuses(
+        &mut self,
+        escrow_id: u64,
+    ) -> Vec<rust_challenge_proxy::TrancheStatus> {
+        let escrow = self
+            .world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_escrow(escrow_id)
+            .returns(ReturnsResult)
+            .run()
+            .into_option()
+            .unwrap();
+        escrow
+            .tranches
+            .iter()
+            .map(|tranche| tranche.status)
+            .collect()
+    }
+
+    fn create_subscription(
+        &mut self,
+        subscriber: TestAddress,
+        merchant: TestAddress,
+        amount_per_period: u64,
+        period: u64,
+        prefund: u64,
+    ) -> u64 {
+        let subscription_id = self
+            .world
+            .tx()
+            .from(subscriber)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .create_subscription(merchant, amount_per_period, period)
+            .egld(prefund)
+            .returns(ReturnsResult)
+            .run();
+        self.check_solvency();
+        subscription_id
+    }
+
+    fn pull_payment(&mut self, merchant: TestAddress, subscription_id: u64, amount: u64) {
+        self.world
+            .tx()
+            .from(merchant)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .pull_payment(subscription_id, amount)
+            .run();
+        self.check_solvency();
+    }
+
+    fn pull_payment_expect_error(
+        &mut self,
+        merchant: TestAddress,
+        subscription_id: u64,
+        amount: u64,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(merchant)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .pull_payment(subscription_id, amount)
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn query_available_to_pull(&mut self, subscription_id: u64) -> RustBigUint {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_available_to_pull(subscription_id)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+
+    fn transfer_reserve(&mut self, from: TestAddress, to: TestAddress, amount: u64) {
+        self.world
+            .tx()
+            .from(from)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .transfer_reserve(to, amount)
+            .run();
+        self.check_solvency();
+    }
+
+    fn transfer_reserve_expect_error(
+        &mut self,
+        from: TestAddress,
+        to: TestAddress,
+        amount: u64,
+        message: &str,
+    ) {
+        self.world
+            .tx()
+            .from(from)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .transfer_reserve(to, amount)
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn propose_fee(&mut self, proposer: TestAddress, new_fee: u64) -> u64 {
+        self.world
+            .tx()
+            .from(proposer)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .propose_fee(new_fee)
+            .returns(ReturnsResult)
+            .run()
+    }
+
+    fn vote(&mut self, voter: TestAddress, proposal_id: u64, support: bool) {
+        self.world
+            .tx()
+            .from(voter)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .vote(proposal_id, support)
+            .run();
+    }
+
+    fn execute_fee_proposal_expect_error(&mut self, proposal_id: u64, message: &str) {
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .execute_fee_proposal(proposal_id)
+            .with_result(ExpectError(4, message))
+            .run();
+    }
+
+    fn query_fee(&mut self) -> RustBigUint {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_fee()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+    }
+}
+
+#[test]
+fn test_deploy() {
+    let mut state = RustChallengeTest::new();
+    let address = state.deploy(1u32);
+    assert_eq!(address, CONTRACT_ADDRESS);
+}
+
+#[test]
+fn test_deposit_fail() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit_fail_required(ADDRESS1, 0u64);
+}
+
+#[test]
+fn test_deposit() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+
+    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+    assert_eq!(
+        RustBigUint::from(5u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
+    state.check_account(OWNER_ADDRESS, 2);
+    state.check_account(ADDRESS1, 2);
+    state.check_account(ADDRESS2, 2);
+    state.check_account(RECEIVER_ADDRESS, 0);
+}
+
+#[test]
+fn test_withdraw_address1() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+
+    state.check_account(ADDRESS1, 2);
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+    state.withdraw(ADDRESS1);
+    state.check_account(ADDRESS1, 3);
+    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS1));
+}
+
+#[test]
+fn test_withdraw_address2() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+
+    state.check_account(ADDRESS2, 2);
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw()
+        .with_result(ExpectError(4, "Nothing to claim"))
+        .run();
+    state.check_account(ADDRESS2, 2);
+    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
+    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+}
+
+#[test]
+fn test_withdraw_receiver() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+
+    state.check_account(RECEIVER_ADDRESS, 0);
+    assert_eq!(
+        RustBigUint::from(5u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 5);
+    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
+    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+}
+
+#[test]
+fn test_withdraw_owner() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+
+    state.check_account(OWNER_ADDRESS, 2);
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_reserve_for_address(OWNER_ADDRESS)
+    );
+    state.withdraw(OWNER_ADDRESS);
+    state.check_account(OWNER_ADDRESS, 5);
+    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
+    assert_eq!(RustBigUint::ZERO, state.query_collected_fees());
+}
+
+#[test]
+fn test_fail_set_fee() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_fee(2u32)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+}
+
+#[test]
+fn test_set_fee() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_fee(2u32)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_fee(0u32)
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_fee(2u32)
+        .run();
+
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 3);
+
+    assert_eq!(RustBigUint::from(5u32), state.query_collected_fees());
+    assert_eq!(
+        RustBigUint::from(4u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
+    state.check_account(OWNER_ADDRESS, 1);
+    state.check_account(ADDRESS1, 2);
+    state.check_account(ADDRESS2, 2);
+    state.check_account(RECEIVER_ADDRESS, 0);
+}
+
+#[test]
+fn test_deposit_limits() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.set_deposit_limits(3, 5, 5, 8);
+
+    state.deposit_expect_error(ADDRESS1, RECEIVER_ADDRESS, 2, "Deposit below minimum");
+    state.deposit_expect_error(ADDRESS2, RECEIVER_ADDRESS, 6, "Deposit above maximum");
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 4);
+    state.deposit_expect_error(
+        ADDRESS2,
+        RECEIVER_ADDRESS,
+        4,
+        "Receiver reserve cap exceeded",
+    );
+    state.deposit_expect_error(ADDRESS2, ADDRESS1, 5, "Contract value cap exceeded");
+
+    state.deposit(ADDRESS2, ADDRESS1, 4);
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+    state.check_account(ADDRESS1, 1);
+    state.check_account(ADDRESS2, 2);
+}
+
+#[test]
+fn test_set_deposit_limits() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_deposit_limits(1u32, 2u32, 3u32, 4u32)
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_deposit_limits(3u32, 2u32, 0u32, 0u32)
+        .with_result(ExpectError(4, "Minimum deposit exceeds maximum deposit"))
+        .run();
+
+    state.set_deposit_limits(2, 0, 10, 0);
+    let limits = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_deposit_limits()
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(limits.min_deposit, 2u32);
+    assert_eq!(limits.max_deposit, 0u32);
+    assert_eq!(limits.max_reserve_per_receiver, 10u32);
+    assert_eq!(limits.max_total_value, 0u32);
+
+    // a zero maximum means unlimited
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 6);
+    assert_eq!(
+        RustBigUint::from(5u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_blocklist() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS2, ADDRESS1, 3);
+    state.block(OWNER_ADDRESS, ADDRESS1);
+
+    state.deposit_expect_error(ADDRESS1, RECEIVER_ADDRESS, 3, "Sender is not permitted");
+    state.deposit_expect_error(ADDRESS2, ADDRESS1, 3, "Receiver is not permitted");
+    state.withdraw_expect_error(ADDRESS1, "Caller is not permitted");
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .remove_from_blocklist(MultiValueVec::from(vec![ADDRESS1.to_address()]))
+        .run();
+    state.withdraw(ADDRESS1);
+    state.check_account(ADDRESS1, 7);
+}
+
+#[test]
+fn test_allowlist_only() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.grant_role(rust_challenge_proxy::Role::Compliance, ADDRESS1);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_allowlist_only(true)
+        .with_result(ExpectError(4, "Caller does not have the required role"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .add_to_allowlist(MultiValueVec::from(vec![
+            ADDRESS1.to_address(),
+            RECEIVER_ADDRESS.to_address(),
+        ]))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_allowlist_only(true)
+        .run();
+
+    state.deposit_expect_error(ADDRESS2, RECEIVER_ADDRESS, 3, "Sender is not permitted");
+    state.deposit_expect_error(ADDRESS1, ADDRESS2, 3, "Receiver is not permitted");
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+
+    // the blocklist takes precedence over the allowlist
+    state.block(ADDRESS1, RECEIVER_ADDRESS);
+    state.withdraw_expect_error(RECEIVER_ADDRESS, "Caller is not permitted");
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_registration_required() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_registration_required(true)
+        .run();
+    state.deposit_expect_error(ADDRESS1, RECEIVER_ADDRESS, 3, "Receiver is not registered");
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .register(
+            ManagedBuffer::<StaticApi>::new_from_bytes(&[b'x'; 65]),
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .with_result(ExpectError(4, "Display name too long"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .register(b"receiver", OptionalValue::Some(ADDRESS2))
+        .run();
+
+    let info = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_receiver_info(RECEIVER_ADDRESS)
+        .returns(ReturnsResult)
+        .run()
+        .into_option()
+        .unwrap();
+    assert_eq!(info.display_name, ManagedBuffer::from(b"receiver"));
+    assert_eq!(info.payout_address, ADDRESS2.to_managed_address());
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.block(OWNER_ADDRESS, ADDRESS2);
+    state.withdraw_expect_error(RECEIVER_ADDRESS, "Payout address is not permitted");
+    state.unblock(OWNER_ADDRESS, ADDRESS2);
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 0);
+    state.check_account(ADDRESS2, 8);
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .unregister()
+        .run();
+    state.deposit_expect_error(ADDRESS1, RECEIVER_ADDRESS, 2, "Receiver is not registered");
+}
+
+#[test]
+fn test_receivers_pagination() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    assert_eq!(0, state.query_receivers_count());
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+    state.deposit(ADDRESS2, OWNER_ADDRESS, 2);
+
+    assert_eq!(3, state.query_receivers_count());
+    assert_eq!(
+        vec![
+            (RECEIVER_ADDRESS.to_address(), RustBigUint::from(5u32)),
+            (ADDRESS1.to_address(), RustBigUint::from(1u32)),
+        ],
+        state.query_receivers(0, 2)
+    );
+    assert_eq!(
+        vec![(OWNER_ADDRESS.to_address(), RustBigUint::from(1u32))],
+        state.query_receivers(2, 10)
+    );
+    assert!(state.query_receivers(5, 10).is_empty());
+
+    state.withdraw(RECEIVER_ADDRESS);
+    assert_eq!(2, state.query_receivers_count());
+    state.withdraw(OWNER_ADDRESS);
+    assert_eq!(
+        vec![(ADDRESS1.to_address(), RustBigUint::from(1u32))],
+        state.query_receivers(0, 10)
+    );
+}
+
+#[test]
+fn test_solvency() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, ADDRESS1, 4);
+
+    let solvency = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_solvency()
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(solvency.total_reserves, 5u32);
+    assert_eq!(solvency.collected_fees, 2u32);
+    assert_eq!(solvency.accrued_fees, 0u32);
+    assert_eq!(solvency.total_escrowed, 0u32);
+    assert_eq!(solvency.balance, 7u32);
+    assert!(solvency.is_solvent);
+
+    state.withdraw(OWNER_ADDRESS);
+    state.withdraw(ADDRESS1);
+    state.withdraw(RECEIVER_ADDRESS);
+    state.world.check_account(CONTRACT_ADDRESS).balance(0);
+}
+
+#[test]
+fn test_fee_beneficiaries() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(3u32);
+    state.set_beneficiaries(vec![(ADDRESS1, 5_000), (ADDRESS2, 5_000)]);
+
+    state.deposit(OWNER_ADDRESS, RECEIVER_ADDRESS, 4);
+    // the rounding remainder stays with the owner
+    assert_eq!(RustBigUint::from(1u32), state.query_collected_fees());
+    assert_eq!(RustBigUint::from(1u32), state.query_accrued_fees(ADDRESS1));
+    assert_eq!(RustBigUint::from(1u32), state.query_accrued_fees(ADDRESS2));
+
+    state.claim_fees(ADDRESS1);
+    state.check_account(ADDRESS1, 6);
+    assert_eq!(RustBigUint::ZERO, state.query_accrued_fees(ADDRESS1));
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .claim_fees()
+        .with_result(ExpectError(4, "No fees to claim"))
+        .run();
+
+    // removing the table sends all new fees to the owner, accrued fees stay claimable
+    state.set_beneficiaries(vec![]);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 4);
+    assert_eq!(RustBigUint::from(4u32), state.query_collected_fees());
+
+    state.block(OWNER_ADDRESS, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .claim_fees()
+        .with_result(ExpectError(4, "Caller is not permitted"))
+        .run();
+    state.unblock(OWNER_ADDRESS, ADDRESS2);
+    state.claim_fees(ADDRESS2);
+    state.check_account(ADDRESS2, 7);
+}
+
+#[test]
+fn test_set_beneficiaries_fail() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_beneficiaries(MultiValueVec::from(vec![MultiValue2::from((
+            ADDRESS1.to_address(),
+            10_000u32,
+        ))]))
+        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_beneficiaries(MultiValueVec::from(vec![
+            MultiValue2::from((ADDRESS1.to_address(), 5_000u32)),
+            MultiValue2::from((ADDRESS2.to_address(), 4_000u32)),
+        ]))
+        .with_result(ExpectError(4, "Shares must add up to 10000 basis points"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_beneficiaries(MultiValueVec::from(vec![
+            MultiValue2::from((ADDRESS1.to_address(), 5_000u32)),
+            MultiValue2::from((ADDRESS1.to_address(), 5_000u32)),
+        ]))
+        .with_result(ExpectError(4, "Duplicate beneficiary"))
+        .run();
+}
+
+#[test]
+fn test_referral_rewards() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(2u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_referral_share(5_000u32)
+        .run();
+
+    state.deposit_with_referrer(ADDRESS1, RECEIVER_ADDRESS, ADDRESS2, 4);
+    state.deposit_with_referrer(OWNER_ADDRESS, RECEIVER_ADDRESS, ADDRESS2, 3);
+
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(ADDRESS2)
+    );
+    assert_eq!(RustBigUint::from(2u32), state.query_collected_fees());
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+
+    let earnings: RustBigUint = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .referral_earnings(ADDRESS2)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    assert_eq!(RustBigUint::from(2u32), earnings);
+    let volume: RustBigUint = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .referred_volume(ADDRESS2)
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    assert_eq!(RustBigUint::from(7u32), volume);
+
+    state.withdraw(ADDRESS2);
+    state.check_account(ADDRESS2, 8);
+}
+
+#[test]
+fn test_referral_reserve_cap() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(2u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_referral_share(5_000u32)
+        .run();
+    state.deposit(ADDRESS1, OWNER_ADDRESS, 3);
+    state.set_deposit_limits(0, 0, 1, 0);
+
+    // the receiver stays within the cap, but the referrer's reward would not
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, OptionalValue::Some(OWNER_ADDRESS))
+        .egld(3)
+        .with_result(ExpectError(4, "Receiver reserve cap exceeded"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, OptionalValue::Some(ADDRESS1))
+        .egld(3)
+        .run();
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+}
+
+#[test]
+fn test_referral_fail() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(2u32);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_referral_share(10_001u32)
+        .with_result(ExpectError(4, "Share cannot exceed 10000 basis points"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, OptionalValue::Some(ADDRESS1))
+        .egld(4)
+        .with_result(ExpectError(4, "Cannot refer yourself"))
+        .run();
+
+    state.block(OWNER_ADDRESS, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, OptionalValue::Some(ADDRESS2))
+        .egld(4)
+        .with_result(ExpectError(4, "Referrer is not permitted"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_registration_required(true)
+        .run();
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .register(
+            b"receiver",
+            OptionalValue::<ManagedAddress<StaticApi>>::None,
+        )
+        .run();
+    // the receiver is registered, the referrer is not
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, OptionalValue::Some(OWNER_ADDRESS))
+        .egld(4)
+        .with_result(ExpectError(4, "Receiver is not registered"))
+        .run();
+}
+
+#[test]
+fn test_deposit_memo() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit_with_memo(RECEIVER_ADDRESS, b"invoice-42", NO_REFERRER)
+        .egld(3)
+        .run();
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+
+    let history = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_deposit_history(RECEIVER_ADDRESS, 0usize, 10usize)
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(2, history.len());
+    assert_eq!(history[0].sender, ADDRESS1.to_managed_address());
+    assert_eq!(history[0].amount, 2u32);
+    assert_eq!(history[0].memo, ManagedBuffer::from(b"invoice-42"));
+    assert_eq!(history[1].sender, ADDRESS2.to_managed_address());
+    assert_eq!(history[1].amount, 3u32);
+    assert!(history[1].memo.is_empty());
+
+    // the history keeps deposits that were already withdrawn
+    state.withdraw(RECEIVER_ADDRESS);
+    let history = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_deposit_history(RECEIVER_ADDRESS, 1usize, 10usize)
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(1, history.len());
+    assert_eq!(history[0].sender, ADDRESS2.to_managed_address());
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit_with_memo(RECEIVER_ADDRESS, &[b'x'; 65][..], NO_REFERRER)
+        .egld(2)
+        .with_result(ExpectError(4, "Memo too long"))
+        .run();
+}
+
+#[test]
+fn test_deposit_records_are_capped() {
+    const DEPOSITOR: TestAddress = TestAddress::new("frequent-depositor");
+    let mut state = RustChallengeTest::new();
+    state.world.account(DEPOSITOR).balance(200).nonce(1);
+    state.deploy(0u32);
+
+    let deposits = rust_challenge::reserves::MAX_DEPOSIT_RECORDS as u64 + 5;
+    for n in 1..=deposits {
+        state.deposit(DEPOSITOR, RECEIVER_ADDRESS, if n % 2 == 0 { 2 } else { 1 });
+    }
+
+    let records = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_deposit_history(RECEIVER_ADDRESS, 0usize, 200usize)
+        .returns(ReturnsResult)
+        .run()
+        .into_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(rust_challenge::reserves::MAX_DEPOSIT_RECORDS, records.len());
+    // the five oldest were overwritten, so the oldest kept is the sixth deposit
+    assert_eq!(records[0].amount, 2u32);
+    assert_eq!(records[1].amount, 1u32);
+    assert_eq!(records[records.len() - 1].amount, 1u32);
+}
+
+#[test]
+fn test_withdraw_and_call() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw_and_call(ADDRESS1, b"deposit", MultiValueVec::<Vec<u8>>::new())
+        .with_result(ExpectError(4, "Target must be a smart contract"))
+        .run();
+
+    // re-deposit the whole reserve to another receiver through the contract itself
+    state.withdraw_and_call(
+        RECEIVER_ADDRESS,
+        CONTRACT_ADDRESS,
+        b"deposit",
+        vec![ADDRESS2.to_address().to_vec()],
+    );
+
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS2)
+    );
+    assert_eq!(RustBigUint::from(2u32), state.query_collected_fees());
+}
+
+#[test]
+fn test_withdraw_and_call_failure_restores_reserve() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_withdrawal_rate_limit(2u32, 100u64)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_circuit_breaker(3u32, 100u64)
+        .run();
+
+    // `withdraw` is not payable, so the call fails and the reserve is credited back
+    state.withdraw_and_call(RECEIVER_ADDRESS, CONTRACT_ADDRESS, b"withdraw", Vec::new());
+
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    state.check_account(RECEIVER_ADDRESS, 0);
+    state.world.check_account(CONTRACT_ADDRESS).balance(3);
+
+    // the failed call used up neither the allowance nor the breaker's outflow
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 2);
+}
+
+#[test]
+fn test_push_payouts() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.grant_role(rust_challenge_proxy::Role::Keeper, ADDRESS1);
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(OWNER_ADDRESS, ADDRESS2, 2);
+    state.block(OWNER_ADDRESS, ADDRESS2);
+
+    let receivers = [RECEIVER_ADDRESS, ADDRESS2, OWNER_ADDRESS];
+
+    state.push_payouts_expect_error(
+        ADDRESS2,
+        &receivers,
+        "Caller does not have the required role",
+    );
+
+    // not enough gas for a single payout, nothing is processed
+    let (status, processed) = state.push_payouts(ADDRESS1, 1_000_000, &receivers);
+    assert_eq!(
+        rust_challenge_proxy::PayoutStatus::InterruptedBeforeOutOfGas,
+        status
+    );
+    assert_eq!(0, processed);
+    state.check_account(RECEIVER_ADDRESS, 0);
+
+    let (status, processed) = state.push_payouts(ADDRESS1, 10_000_000, &receivers);
+    assert_eq!(rust_challenge_proxy::PayoutStatus::Completed, status);
+    assert_eq!(3, processed);
+
+    state.check_account(RECEIVER_ADDRESS, 2);
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    // frozen reserves stay in place
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS2)
+    );
+    state.check_account(ADDRESS2, 6);
+}
+
+#[test]
+fn test_sweep_dormant_reserve() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.grant_role(rust_challenge_proxy::Role::Admin, ADDRESS1);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_dormancy_config(1_000u64, ADDRESS2)
+        .run();
+
+    state.set_block_timestamp(100);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+
+    state.set_block_timestamp(600);
+    let time_to_expiry = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_time_to_expiry(RECEIVER_ADDRESS)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(Some(500), time_to_expiry.into_option());
+
+    state.sweep_dormant_reserve_expect_error(ADDRESS1, RECEIVER_ADDRESS, "Reserve is not dormant");
+
+    state.set_block_timestamp(1_100);
+    state.block(OWNER_ADDRESS, RECEIVER_ADDRESS);
+    state.sweep_dormant_reserve_expect_error(
+        ADDRESS1,
+        RECEIVER_ADDRESS,
+        "Receiver is not permitted",
+    );
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .remove_from_blocklist(MultiValueVec::from(vec![RECEIVER_ADDRESS.to_address()]))
+        .run();
+    state.sweep_dormant_reserve_expect_error(
+        ADDRESS2,
+        RECEIVER_ADDRESS,
+        "Caller does not have the required role",
+    );
+    state.sweep_dormant_reserve(ADDRESS1, RECEIVER_ADDRESS);
+
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    state.check_account(ADDRESS2, 8);
+}
+
+#[test]
+fn test_withdraw_refreshes_activity() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_dormancy_config(1_000u64, ADDRESS2)
+        .run();
+
+    state.set_block_timestamp(100);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 2);
+    state.set_block_timestamp(900);
+    state.withdraw(RECEIVER_ADDRESS);
+
+    // a deposit into the drained reserve restarts the clock, so it cannot be swept at once
+    state.set_block_timestamp(5_000);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 2);
+    state.sweep_dormant_reserve_expect_error(
+        OWNER_ADDRESS,
+        RECEIVER_ADDRESS,
+        "Reserve is not dormant",
+    );
+
+    // deposits into a non-empty reserve do not count as activity of the receiver
+    state.set_block_timestamp(5_200);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 2);
+    state.set_block_timestamp(5_600);
+    let time_to_expiry = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_time_to_expiry(RECEIVER_ADDRESS)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(Some(400), time_to_expiry.into_option());
+
+    state.sweep_dormant_reserve_expect_error(
+        OWNER_ADDRESS,
+        RECEIVER_ADDRESS,
+        "Reserve is not dormant",
+    );
+
+    state.set_block_timestamp(6_000);
+    state.sweep_dormant_reserve(OWNER_ADDRESS, RECEIVER_ADDRESS);
+    state.check_account(ADDRESS2, 6);
+}
+
+#[test]
+fn test_withdrawal_rate_limit() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    assert_eq!(None, state.query_remaining_allowance(RECEIVER_ADDRESS));
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_withdrawal_rate_limit(3u32, 100u64)
+        .run();
+
+    state.set_block_timestamp(10);
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 3);
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(
+        Some(RustBigUint::ZERO),
+        state.query_remaining_allowance(RECEIVER_ADDRESS)
+    );
+
+    state.set_block_timestamp(109);
+    state.withdraw_expect_error(RECEIVER_ADDRESS, "Withdrawal limit reached");
+
+    state.set_block_timestamp(110);
+    assert_eq!(
+        Some(RustBigUint::from(3u32)),
+        state.query_remaining_allowance(RECEIVER_ADDRESS)
+    );
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 5);
+    assert_eq!(
+        Some(RustBigUint::from(1u32)),
+        state.query_remaining_allowance(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_set_withdrawal_rate_limit_fail() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_withdrawal_rate_limit(3u32, 0u64)
+        .with_result(ExpectError(4, "Window must be greater than zero"))
+        .run();
+}
+
+#[test]
+fn test_circuit_breaker() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_circuit_breaker(4u32, 100u64)
+        .run();
+
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, ADDRESS1, 4);
+
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 2);
+
+    // crossing the threshold trips the breaker instead of paying out
+    state.withdraw(ADDRESS1);
+    state.check_account(ADDRESS1, 2);
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+    let paused = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdrawals_paused()
+        .returns(ReturnsResult)
+        .run();
+    assert!(paused);
+    state.withdraw_expect_error(ADDRESS1, "Withdrawals are paused");
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .reset_circuit_breaker()
+        .with_result(ExpectError(4, "Caller does not have the required role"))
+        .run();
+
+    state.grant_role(rust_challenge_proxy::Role::Guardian, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .reset_circuit_breaker()
+        .run();
+
+    state.withdraw(ADDRESS1);
+    state.check_account(ADDRESS1, 5);
+}
+
+#[test]
+fn test_push_payouts_circuit_breaker() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_circuit_breaker(4u32, 100u64)
+        .run();
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, ADDRESS1, 4);
+
+    let (status, processed) =
+        state.push_payouts(OWNER_ADDRESS, 10_000_000, &[RECEIVER_ADDRESS, ADDRESS1]);
+    assert_eq!(
+        rust_challenge_proxy::PayoutStatus::CircuitBreakerTripped,
+        status
+    );
+    assert_eq!(1, processed);
+    state.check_account(RECEIVER_ADDRESS, 2);
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(ADDRESS1)
+    );
+}
+
+const RECOVERY_WAITING_PERIOD: u64 = 30 * 24 * 60 * 60;
+
+#[test]
+fn test_recovery_claim() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+
+    state.set_block_timestamp(100);
+    state.nominate_recovery_address(RECEIVER_ADDRESS, ADDRESS2, RECOVERY_WAITING_PERIOD);
+
+    state.set_block_timestamp(100 + RECOVERY_WAITING_PERIOD - 1);
+    state.claim_as_recovery_expect_error(
+        ADDRESS2,
+        RECEIVER_ADDRESS,
+        "Receiver is not inactive long enough",
+    );
+    state.claim_as_recovery_expect_error(
+        ADDRESS1,
+        RECEIVER_ADDRESS,
+        "Caller is not the recovery address",
+    );
+
+    state.set_block_timestamp(100 + RECOVERY_WAITING_PERIOD);
+    state.claim_as_recovery(ADDRESS2, RECEIVER_ADDRESS);
+
+    state.check_account(ADDRESS2, 8);
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_recovery_cancel() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .nominate_recovery_address(ADDRESS2, RECOVERY_WAITING_PERIOD - 1)
+        .with_result(ExpectError(4, "Waiting period too short"))
+        .run();
+
+    state.nominate_recovery_address(RECEIVER_ADDRESS, ADDRESS2, RECOVERY_WAITING_PERIOD);
+    let nomination = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_recovery_nomination(RECEIVER_ADDRESS)
+        .returns(ReturnsResult)
+        .run()
+        .into_option()
+        .unwrap();
+    assert_eq!(nomination.recovery_address, ADDRESS2.to_managed_address());
+    assert_eq!(nomination.waiting_period, RECOVERY_WAITING_PERIOD);
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .cancel_recovery_nomination()
+        .run();
+
+    state.set_block_timestamp(RECOVERY_WAITING_PERIOD * 2);
+    state.claim_as_recovery_expect_error(ADDRESS2, RECEIVER_ADDRESS, "No recovery nomination");
+}
+
+#[test]
+fn test_withdraw_for_operator() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
+    state.approve_operator(RECEIVER_ADDRESS, ADDRESS1, 3, 0);
+
+    state.withdraw_for_expect_error(ADDRESS2, RECEIVER_ADDRESS, 1, None, "Operator not approved");
+    state.withdraw_for_expect_error(
+        ADDRESS1,
+        RECEIVER_ADDRESS,
+        4,
+        None,
+        "Amount exceeds operator allowance",
+    );
+
+    state.withdraw_for(ADDRESS1, RECEIVER_ADDRESS, 2, None);
+    state.check_account(RECEIVER_ADDRESS, 2);
+    state.check_account(ADDRESS1, 2);
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+
+    state.withdraw_for_expect_error(
+        ADDRESS1,
+        RECEIVER_ADDRESS,
+        1,
+        Some(ADDRESS1),
+        "Destination not approved by receiver",
+    );
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .approve_destination(ADDRESS2)
+        .run();
+    state.withdraw_for(ADDRESS1, RECEIVER_ADDRESS, 1, Some(ADDRESS2));
+    state.check_account(ADDRESS2, 3);
+
+    // the allowance is used up, so the approval is gone
+    let operators = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_operators(RECEIVER_ADDRESS)
+        .returns(ReturnsResult)
+        .run();
+    assert!(operators.is_empty());
+}
+
+#[test]
+fn test_operator_expiry_and_revoke() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 5);
+    state.approve_operator(RECEIVER_ADDRESS, ADDRESS1, 0, 50);
+    state.approve_operator(RECEIVER_ADDRESS, ADDRESS2, 0, 0);
+
+    let approval = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_operator_approval(RECEIVER_ADDRESS, ADDRESS1)
+        .returns(ReturnsResult)
+        .run()
+        .into_option()
+        .unwrap();
+    assert_eq!(approval.allowance, 0u32);
+    assert_eq!(approval.expiry, 50);
+
+    state.set_block_timestamp(49);
+    state.withdraw_for(ADDRESS1, RECEIVER_ADDRESS, 1, None);
+    state.set_block_timestamp(50);
+    state.withdraw_for_expect_error(ADDRESS1, RECEIVER_ADDRESS, 1, None, "Approval expired");
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .revoke_operator(ADDRESS2)
+        .run();
+    state.withdraw_for_expect_error(ADDRESS2, RECEIVER_ADDRESS, 1, None, "Operator not approved");
+    state.check_account(RECEIVER_ADDRESS, 1);
+}
+
+const SIGNER_SECRET_KEY: [u8; 32] = [7u8; 32];
+
+struct SignedWithdrawal {
+    receiver: Address,
+    amount: u64,
+    relayer_tip: u64,
+    nonce: u64,
+    deadline: u64,
+}
+
+#[test]
+fn test_withdraw_with_signature() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    let (signing_key, receiver) = state.signer();
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(&receiver, NO_REFERRER)
+        .egld(5)
+        .run();
+
+    let withdrawal = SignedWithdrawal {
+        receiver: receiver.clone(),
+        amount: 3,
+        relayer_tip: 1,
+        nonce: 0,
+        deadline: 100,
+    };
+    let signature = state.sign_withdrawal(&signing_key, &withdrawal);
+    state.withdraw_with_signature(ADDRESS2, &withdrawal, &signature, None);
+    state.world.check_account(&receiver).balance(2);
+    state.check_account(ADDRESS2, 7);
+
+    let nonce = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdrawal_nonce(&receiver)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(nonce, 1);
+
+    // the same authorization cannot be replayed
+    state.withdraw_with_signature(ADDRESS2, &withdrawal, &signature, Some("Invalid nonce"));
+}
+
+#[test]
+fn test_withdraw_with_signature_fail() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    let (signing_key, receiver) = state.signer();
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(&receiver, NO_REFERRER)
+        .egld(5)
+        .run();
+
+    let withdrawal = SignedWithdrawal {
+        receiver: receiver.clone(),
+        amount: 3,
+        relayer_tip: 1,
+        nonce: 0,
+        deadline: 100,
+    };
+    let signature = state.sign_withdrawal(&signing_key, &withdrawal);
+
+    state.set_block_timestamp(101);
+    state.withdraw_with_signature(
+        ADDRESS2,
+        &withdrawal,
+        &signature,
+        Some("Authorization expired"),
+    );
+
+    state.set_block_timestamp(0);
+    let oversized_tip = SignedWithdrawal {
+        relayer_tip: 4,
+        ..withdrawal
+    };
+    let signature = state.sign_withdrawal(&signing_key, &oversized_tip);
+    state.withdraw_with_signature(
+        ADDRESS2,
+        &oversized_tip,
+        &signature,
+        Some("Tip exceeds amount"),
+    );
+    state.world.check_account(&receiver).balance(0);
+}
+
+#[test]
+fn test_withdraw_with_signature_circuit_breaker() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    let (signing_key, receiver) = state.signer();
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(&receiver, NO_REFERRER)
+        .egld(5)
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_circuit_breaker(2u32, 100u64)
+        .run();
+
+    let withdrawal = SignedWithdrawal {
+        receiver: receiver.clone(),
+        amount: 3,
+        relayer_tip: 1,
+        nonce: 0,
+        deadline: 100,
+    };
+    let signature = state.sign_withdrawal(&signing_key, &withdrawal);
+    state.withdraw_with_signature(ADDRESS2, &withdrawal, &signature, None);
+    state.world.check_account(&receiver).balance(0);
+
+    // the tripped breaker left the nonce unused, so the same authorization works later
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_circuit_breaker(4u32, 100u64)
+        .run();
+    state.grant_role(rust_challenge_proxy::Role::Guardian, ADDRESS1);
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .reset_circuit_breaker()
+        .run();
+    state.withdraw_with_signature(ADDRESS2, &withdrawal, &signature, None);
+    state.world.check_account(&receiver).balance(2);
+}
+
+#[test]
+fn test_escrow_release() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    let escrow_id = state.create_escrow(
+        ADDRESS2,
+        RECEIVER_ADDRESS,
+        ADDRESS1,
+        0,
+        vec![("design", 2), ("build", 3)],
+    );
+    assert_eq!(escrow_id, 1);
+    state.check_account(ADDRESS2, 1);
+
+    state.escrow_expect_error(
+        RECEIVER_ADDRESS,
+        escrow_id,
+        0,
+        true,
+        "Only the sender or the arbiter can release",
+    );
+    state.release_tranche(ADDRESS2, escrow_id, 0);
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    state.escrow_expect_error(ADDRESS1, escrow_id, 0, true, "Tranche already settled");
+    state.escrow_expect_error(ADDRESS1, escrow_id, 2, true, "Tranche not found");
+
+    state.release_tranche(ADDRESS1, escrow_id, 1);
+    assert_eq!(
+        state.query_tranche_statuses(escrow_id),
+        vec![
+            rust_challenge_proxy::TrancheStatus::Released,
+            rust_challenge_proxy::TrancheStatus::Released
+        ]
+    );
+
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 5);
+    state.world.check_account(CONTRACT_ADDRESS).balance(0);
+}
+
+#[test]
+fn test_escrow_cancel() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    let escrow_id = state.create_escrow(
+        ADDRESS2,
+        RECEIVER_ADDRESS,
+        ADDRESS1,
+        100,
+        vec![("design", 1), ("build", 2), ("launch", 3)],
+    );
+
+    // the arbiter can cancel at any time, the sender only after the deadline
+    state.cancel_tranche(ADDRESS1, escrow_id, 0);
+    state.check_account(ADDRESS2, 1);
+    state.escrow_expect_error(
+        ADDRESS2,
+        escrow_id,
+        1,
+        false,
+        "Escrow deadline has not passed",
+    );
+    state.escrow_expect_error(
+        RECEIVER_ADDRESS,
+        escrow_id,
+        1,
+        false,
+        "Only the sender or the arbiter can cancel",
+    );
+
+    state.release_tranche(ADDRESS2, escrow_id, 1);
+    state.set_block_timestamp(101);
+    state.block(OWNER_ADDRESS, ADDRESS2);
+    state.escrow_expect_error(ADDRESS1, escrow_id, 2, false, "Sender is not permitted");
+    state.unblock(OWNER_ADDRESS, ADDRESS2);
+    state.cancel_tranche(ADDRESS2, escrow_id, 2);
+    state.check_account(ADDRESS2, 4);
+    state.escrow_expect_error(ADDRESS1, escrow_id, 1, false, "Tranche already settled");
+    assert_eq!(
+        state.query_tranche_statuses(escrow_id),
+        vec![
+            rust_challenge_proxy::TrancheStatus::Cancelled,
+            rust_challenge_proxy::TrancheStatus::Released,
+            rust_challenge_proxy::TrancheStatus::Cancelled
+        ]
+    );
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_escrow_deposit_limits() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.set_deposit_limits(0, 5, 4, 0);
+
+    let mut tranches = MultiValueEncoded::<StaticApi, _>::new();
+    tranches.push(MultiValue2::from((
+        ManagedBuffer::from("design"),
+        BigUint::from(6u32),
+    )));
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .create_escrow(RECEIVER_ADDRESS, ADDRESS1, 0u64, tranches)
+        .egld(6)
+        .with_result(ExpectError(4, "Deposit above maximum"))
+        .run();
+
+    // the reserve cap applies when a tranche lands in the reserve
+    let escrow_id = state.create_escrow(
+        ADDRESS2,
+        RECEIVER_ADDRESS,
+        ADDRESS1,
+        0,
+        vec![("design", 2), ("build", 3)],
+    );
+    state.release_tranche(ADDRESS2, escrow_id, 0);
+    state.escrow_expect_error(
+        ADDRESS2,
+        escrow_id,
+        1,
+        true,
+        "Receiver reserve cap exceeded",
+    );
+    assert_eq!(
+        RustBigUint::from(4u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_create_escrow_fail() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    let mut tranches = MultiValueEncoded::<StaticApi, _>::new();
+    tranches.push(MultiValue2::from((
+        ManagedBuffer::from("design"),
+        BigUint::from(2u32),
+    )));
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .create_escrow(RECEIVER_ADDRESS, ADDRESS2, 0u64, tranches)
+        .egld(3)
+        .with_result(ExpectError(4, "Tranches must add up to the payment"))
+        .run();
+    state.escrow_expect_error(ADDRESS1, 1, 0, true, "Escrow not found");
+}
+
+#[test]
+fn test_subscription_pull() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.set_block_timestamp(1_000);
+    let subscription_id = state.create_subscription(ADDRESS2, RECEIVER_ADDRESS, 2, 30, 5);
+    state.check_account(ADDRESS2, 1);
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_available_to_pull(subscription_id)
+    );
+
+    state.pull_payment_expect_error(ADDRESS1, subscription_id, 1, "Only the merchant can pull");
+    state.pull_payment(RECEIVER_ADDRESS, subscription_id, 1);
+    state.pull_payment_expect_error(
+        RECEIVER_ADDRESS,
+        subscription_id,
+        2,
+        "Amount exceeds period allowance",
+    );
+    state.pull_payment(RECEIVER_ADDRESS, subscription_id, 1);
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_available_to_pull(subscription_id)
+    );
+
+    // a new period restores the allowance
+    state.set_block_timestamp(1_030);
+    state.pull_payment(RECEIVER_ADDRESS, subscription_id, 2);
+    state.set_block_timestamp(1_095);
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_available_to_pull(subscription_id)
+    );
+    state.pull_payment_expect_error(
+        RECEIVER_ADDRESS,
+        subscription_id,
+        2,
+        "Insufficient subscription balance",
+    );
+    state.pull_payment(RECEIVER_ADDRESS, subscription_id, 1);
+    assert_eq!(
+        RustBigUint::from(5u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+
+    state.withdraw(RECEIVER_ADDRESS);
+    state.check_account(RECEIVER_ADDRESS, 5);
+}
+
+#[test]
+fn test_subscription_cancel() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    let subscription_id = state.create_subscription(ADDRESS2, RECEIVER_ADDRESS, 2, 30, 5);
+    state.pull_payment(RECEIVER_ADDRESS, subscription_id, 2);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .cancel_subscription(subscription_id)
+        .with_result(ExpectError(
+            4,
+            "Only the subscriber or the merchant can cancel",
+        ))
+        .run();
+    // no refund to a frozen subscriber
+    state.block(OWNER_ADDRESS, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .cancel_subscription(subscription_id)
+        .with_result(ExpectError(4, "Subscriber is not permitted"))
+        .run();
+    state.unblock(OWNER_ADDRESS, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .cancel_subscription(subscription_id)
+        .run();
+    state.check_solvency();
+
+    state.check_account(ADDRESS2, 4);
+    state.pull_payment_expect_error(
+        RECEIVER_ADDRESS,
+        subscription_id,
+        1,
+        "Subscription not found",
+    );
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_subscription_deposit_limits() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.set_deposit_limits(0, 0, 3, 6);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .create_subscription(RECEIVER_ADDRESS, 2u32, 30u64)
+        .egld(4)
+        .with_result(ExpectError(4, "Contract value cap exceeded"))
+        .run();
+    let subscription_id = state.create_subscription(ADDRESS2, RECEIVER_ADDRESS, 2, 30, 3);
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .top_up_subscription(subscription_id)
+        .egld(1)
+        .with_result(ExpectError(4, "Contract value cap exceeded"))
+        .run();
+
+    state.pull_payment_expect_error(
+        RECEIVER_ADDRESS,
+        subscription_id,
+        2,
+        "Receiver reserve cap exceeded",
+    );
+    state.pull_payment(RECEIVER_ADDRESS, subscription_id, 1);
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+}
+
+#[test]
+fn test_subscription_funding_checks() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .create_subscription(RECEIVER_ADDRESS, 2u32, 30u64)
+        .with_result(ExpectError(4, "Payment must be greater than zero"))
+        .run();
+    let subscription_id = state.create_subscription(ADDRESS2, RECEIVER_ADDRESS, 2, 30, 3);
+
+    state.block(OWNER_ADDRESS, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .top_up_subscription(subscription_id)
+        .egld(1)
+        .with_result(ExpectError(4, "Sender is not permitted"))
+        .run();
+
+    state.unblock(OWNER_ADDRESS, ADDRESS2);
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .top_up_subscription(subscription_id)
+        .egld(1)
+        .run();
+    state.check_solvency();
+}
+
+#[test]
+fn test_transfer_reserve() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 5);
+
+    // no transfer fee by default
+    state.transfer_reserve(RECEIVER_ADDRESS, ADDRESS2, 1);
+    assert_eq!(
+        RustBigUint::from(1u32),
+        state.query_reserve_for_address(ADDRESS2)
+    );
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_transfer_fee(1u32)
+        .run();
+    state.transfer_reserve_expect_error(
+        RECEIVER_ADDRESS,
+        ADDRESS2,
+        1,
+        "Amount must be greater than transfer fee",
+    );
+    state.transfer_reserve_expect_error(RECEIVER_ADDRESS, ADDRESS2, 4, "Insufficient reserve");
+    state.transfer_reserve(RECEIVER_ADDRESS, ADDRESS2, 3);
+    assert_eq!(
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(ADDRESS2)
+    );
+    assert_eq!(
+        RustBigUint::ZERO,
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(RustBigUint::from(2u32), state.query_collected_fees());
+
+    state.withdraw(ADDRESS2);
+    state.check_account(ADDRESS2, 9);
+    state.transfer_reserve_expect_error(RECEIVER_ADDRESS, ADDRESS2, 2, "Insufficient reserve");
+}
+
+#[test]
+fn test_transfer_reserve_limits() {
+    let mut state = RustChallengeTest::new();
+    state.deploy(1u32);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 5);
+    state.set_deposit_limits(3, 0, 2, 1);
+
+    // only the reserve cap applies, as no value enters the contract
+    state.transfer_reserve(RECEIVER_ADDRESS, ADDRESS2, 1);
+    state.transfer_reserve_expect_error(
+        RECEIVER_ADDRESS,
+        ADDRESS2,
+        2,
+        "Receiver reserve cap exceeded",
+    );
+    state.transfer_reserve(RECEIVER_ADDRESS, ADDRESS2, 1);
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(ADDRESS2)
+    );
+}
+
+const MEMBER_ADDRESS: TestAddress = TestAddress::new("member");
+const MEMBERSHIP_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("MEMBER-123456");
+
+#[test]
+fn test_membership_discount() {
+    let mut state = RustChallengeTest::new();
+    state
+        .world
+        .account(MEMBER_ADDRESS)
+        .balance(6)
+        .nonce(1)
+        .esdt_nft_balance(MEMBERSHIP_TOKEN, 1, 1, ());
+    state.deploy(2u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_membership_discount(MEMBERSHIP_TOKEN, 5_000u32)
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(MEMBER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .lock_membership_token()
+        .single_esdt(&MEMBERSHIP_TOKEN.into(), 1, &BigUint::from(1u32))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(MEMBER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, NO_REFERRER)
+        .egld(3)
+        .run();
+    assert_eq!(
+        RustBigUint::from(2u32),
+        state.query_reserve_for_address(RECEIVER_ADDRESS)
+    );
+    assert_eq!(RustBigUint::from(1u32), state.query_collected_fees());
+
+    // non-members pay the full fee
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 3);
+    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+
+    state
+        .world
+        .tx()
+        .from(MEMBER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .unlock_membership_token()
+        .run();
+    state
+        .world
+        .check_account(MEMBER_ADDRESS)
+        .esdt_nft_balance_and_attributes(MEMBERSHIP_TOKEN, 1, 1, ManagedBuffer::<StaticApi>::new());
 
-    state.deposit_fail_required(ADDRESS1, 0u64);
+    state
+        .world
+        .tx()
+        .from(MEMBER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .deposit(RECEIVER_ADDRESS, NO_REFERRER)
+        .egld(3)
+        .run();
+    assert_eq!(RustBigUint::from(5u32), state.query_collected_fees());
 }
 
 #[test]
-fn test_deposit() {
+fn test_fee_governance() {
     let mut state = RustChallengeTest::new();
     state.deploy(1u32);
-
     state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
     state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
-    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_governance_config(100u64, 6_000u32)
+        .run();
 
-    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
-    assert_eq!(
-        RustBigUint::from(5u32),
-        state.query_reserve_for_address(RECEIVER_ADDRESS)
-    );
-    assert_eq!(
-        RustBigUint::from(1u32),
-        state.query_reserve_for_address(ADDRESS1)
-    );
-    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
-    state.check_account(OWNER_ADDRESS, 2);
-    state.check_account(ADDRESS1, 2);
-    state.check_account(ADDRESS2, 2);
-    state.check_account(RECEIVER_ADDRESS, 0);
+    // 3 of the 7 deposited is below the 60% quorum
+    let proposal_id = state.propose_fee(ADDRESS1, 2);
+    state.vote(ADDRESS1, proposal_id, true);
+    state.execute_fee_proposal_expect_error(proposal_id, "Voting period has not ended");
+    state.set_block_timestamp(100);
+    state.execute_fee_proposal_expect_error(proposal_id, "Quorum not reached");
+
+    let proposal_id = state.propose_fee(ADDRESS1, 2);
+    state.vote(ADDRESS1, proposal_id, true);
+    state.vote(ADDRESS2, proposal_id, false);
+    state.set_block_timestamp(200);
+    state.execute_fee_proposal_expect_error(proposal_id, "Proposal did not pass");
+
+    let proposal_id = state.propose_fee(ADDRESS2, 2);
+    state.vote(ADDRESS1, proposal_id, true);
+    state.vote(ADDRESS2, proposal_id, true);
+    state.set_block_timestamp(300);
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .execute_fee_proposal(proposal_id)
+        .run();
+    assert_eq!(RustBigUint::from(2u32), state.query_fee());
+    state.execute_fee_proposal_expect_error(proposal_id, "Proposal already executed");
 }
 
 #[test]
-fn test_withdraw_address1() {
+fn test_fee_governance_quorum_snapshot() {
     let mut state = RustChallengeTest::new();
     state.deploy(1u32);
-
     state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
     state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
-    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_governance_config(100u64, 5_000u32)
+        .run();
 
-    state.check_account(ADDRESS1, 2);
-    assert_eq!(
-        RustBigUint::from(1u32),
-        state.query_reserve_for_address(ADDRESS1)
-    );
-    state.withdraw(ADDRESS1);
-    state.check_account(ADDRESS1, 3);
-    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
-    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS1));
+    let proposal_id = state.propose_fee(ADDRESS2, 2);
+    state.vote(ADDRESS2, proposal_id, true);
+
+    // 4 of the 7 deposited met the 50% quorum in place when the proposal was created
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_governance_config(100u64, 8_000u32)
+        .run();
+    state.set_block_timestamp(100);
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .execute_fee_proposal(proposal_id)
+        .run();
+    assert_eq!(RustBigUint::from(2u32), state.query_fee());
 }
 
 #[test]
-fn test_withdraw_address2() {
+fn test_fee_governance_fail() {
     let mut state = RustChallengeTest::new();
     state.deploy(1u32);
-
     state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
-    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
-    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
 
-    state.check_account(ADDRESS2, 2);
-    assert_eq!(
-        RustBigUint::from(1u32),
-        state.query_reserve_for_address(ADDRESS1)
-    );
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .propose_fee(2u32)
+        .with_result(ExpectError(4, "Governance is disabled"))
+        .run();
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_governance_config(100u64, 5_000u32)
+        .run();
+    // deposits to oneself do not count as volume
+    state.deposit(ADDRESS2, ADDRESS2, 2);
     state
         .world
         .tx()
         .from(ADDRESS2)
         .to(CONTRACT_ADDRESS)
         .typed(rust_challenge_proxy::RustChallengeProxy)
-        .withdraw()
-        .with_result(ExpectError(4, "Nothing to claim"))
+        .propose_fee(2u32)
+        .with_result(ExpectError(4, "Only depositors can propose"))
         .run();
-    state.check_account(ADDRESS2, 2);
-    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
-    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+
+    let proposal_id = state.propose_fee(ADDRESS1, 2);
+    state.vote(ADDRESS1, proposal_id, true);
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .vote(proposal_id, true)
+        .with_result(ExpectError(4, "Already voted"))
+        .run();
+    // volume deposited after the proposal was created carries no weight on it
+    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 2);
+    state
+        .world
+        .tx()
+        .from(ADDRESS2)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .vote(proposal_id, false)
+        .with_result(ExpectError(4, "No voting power"))
+        .run();
+
+    state.set_block_timestamp(100);
+    state
+        .world
+        .tx()
+        .from(ADDRESS1)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .vote(proposal_id, false)
+        .with_result(ExpectError(4, "Voting period has ended"))
+        .run();
+    assert_eq!(RustBigUint::from(1u32), state.query_fee());
 }
 
 #[test]
-fn test_withdraw_receiver() {
+fn test_fee_forwarding() {
     let mut state = RustChallengeTest::new();
     state.deploy(1u32);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_fee_treasury(CONTRACT_ADDRESS, 2u32)
+        .with_result(ExpectError(4, "Invalid treasury address"))
+        .run();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_fee_treasury(ADDRESS2, 2u32)
+        .run();
 
-    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
-    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
-    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 2);
+    assert_eq!(RustBigUint::from(1u32), state.query_collected_fees());
+    state.check_account(ADDRESS2, 6);
 
-    state.check_account(RECEIVER_ADDRESS, 0);
-    assert_eq!(
-        RustBigUint::from(5u32),
-        state.query_reserve_for_address(RECEIVER_ADDRESS)
-    );
-    state.withdraw(RECEIVER_ADDRESS);
-    state.check_account(RECEIVER_ADDRESS, 5);
-    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
-    assert_eq!(RustBigUint::from(3u32), state.query_collected_fees());
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 2);
+    assert_eq!(RustBigUint::ZERO, state.query_collected_fees());
+    state.check_account(ADDRESS2, 8);
+
+    // below the threshold again, so the owner withdraws as usual
+    state.deposit(OWNER_ADDRESS, RECEIVER_ADDRESS, 2);
+    state.withdraw(OWNER_ADDRESS);
+    state.check_account(OWNER_ADDRESS, 3);
 }
 
 #[test]
-fn test_withdraw_owner() {
+fn test_statistics() {
     let mut state = RustChallengeTest::new();
     state.deploy(1u32);
-
+    state.set_block_timestamp(10);
     state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
+    state.set_block_timestamp(20);
     state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
-    state.deposit(OWNER_ADDRESS, ADDRESS1, 2);
+    state.deposit(ADDRESS2, ADDRESS1, 2);
+    state.set_block_timestamp(30);
+    state.transfer_reserve(ADDRESS1, RECEIVER_ADDRESS, 1);
+    state.withdraw(RECEIVER_ADDRESS);
 
-    state.check_account(OWNER_ADDRESS, 2);
-    assert_eq!(
-        RustBigUint::ZERO,
-        state.query_reserve_for_address(OWNER_ADDRESS)
-    );
-    state.withdraw(OWNER_ADDRESS);
-    state.check_account(OWNER_ADDRESS, 5);
-    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
-    assert_eq!(RustBigUint::ZERO, state.query_collected_fees());
+    // payouts pushed by a keeper count as withdrawals too
+    state.grant_role(rust_challenge_proxy::Role::Keeper, OWNER_ADDRESS);
+    state.deposit(OWNER_ADDRESS, ADDRESS1, 3);
+    state.push_payouts(OWNER_ADDRESS, 10_000_000, &[ADDRESS1]);
+
+    let sender_stats = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_address_stats(ADDRESS2)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(sender_stats.deposited, 6u32);
+    assert_eq!(sender_stats.received, 0u32);
+    assert_eq!(sender_stats.fees_paid, 2u32);
+    assert_eq!(sender_stats.withdrawal_count, 0);
+    assert_eq!(sender_stats.last_activity, 20);
+
+    let receiver_stats = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_address_stats(RECEIVER_ADDRESS)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(receiver_stats.deposited, 0u32);
+    assert_eq!(receiver_stats.received, 6u32);
+    assert_eq!(receiver_stats.fees_paid, 0u32);
+    assert_eq!(receiver_stats.withdrawal_count, 1);
+    assert_eq!(receiver_stats.last_activity, 30);
+
+    let address1_stats = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_address_stats(ADDRESS1)
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(address1_stats.received, 3u32);
+    assert_eq!(address1_stats.withdrawal_count, 1);
+
+    let global_stats = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_global_stats()
+        .returns(ReturnsResult)
+        .run();
+    assert_eq!(global_stats.deposit_count, 4);
+    assert_eq!(global_stats.total_deposited, 12u32);
+    assert_eq!(global_stats.total_fees, 4u32);
+    assert_eq!(global_stats.withdrawal_count, 2);
+    assert_eq!(global_stats.total_withdrawn, 8u32);
 }
 
 #[test]
-fn test_fail_set_fee() {
+fn test_receipts() {
     let mut state = RustChallengeTest::new();
+    // the VM runs its built-in ESDT system SC mock, but the account must exist and have code
+    state
+        .world
+        .account(ESDTSystemSCAddress)
+        .nonce(1)
+        .code(CODE_PATH);
     state.deploy(1u32);
     state
         .world
         .tx()
-        .from(ADDRESS1)
+        .from(OWNER_ADDRESS)
         .to(CONTRACT_ADDRESS)
         .typed(rust_challenge_proxy::RustChallengeProxy)
-        .set_fee(2u32)
-        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .set_receipt_mode(true)
+        .with_result(ExpectError(4, "Receipt token not issued"))
         .run();
-}
-
-#[test]
-fn test_set_fee() {
-    let mut state = RustChallengeTest::new();
-    state.deploy(1u32);
-
-    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 3);
 
     state
         .world
         .tx()
-        .from(ADDRESS1)
+        .from(OWNER_ADDRESS)
         .to(CONTRACT_ADDRESS)
         .typed(rust_challenge_proxy::RustChallengeProxy)
-        .set_fee(2u32)
-        .with_result(ExpectError(4, "Endpoint can only be called by owner"))
+        .issue_receipt_token(ManagedBuffer::from("Receipts"), ManagedBuffer::from("RCPT"))
+        .run();
+    let receipt_token = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .receipt_token()
+        .returns(ReturnsResult)
         .run();
-
     state
         .world
         .tx()
         .from(OWNER_ADDRESS)
         .to(CONTRACT_ADDRESS)
         .typed(rust_challenge_proxy::RustChallengeProxy)
-        .set_fee(0u32)
+        .issue_receipt_token(ManagedBuffer::from("Receipts"), ManagedBuffer::from("RCPT"))
+        .with_result(ExpectError(4, "Receipt token already issued"))
         .run();
-
     state
         .world
         .tx()
         .from(OWNER_ADDRESS)
         .to(CONTRACT_ADDRESS)
         .typed(rust_challenge_proxy::RustChallengeProxy)
-        .set_fee(2u32)
+        .set_receipt_mode(true)
         .run();
 
-    state.deposit(ADDRESS2, RECEIVER_ADDRESS, 4);
-    state.deposit(OWNER_ADDRESS, ADDRESS1, 3);
-
-    assert_eq!(RustBigUint::from(5u32), state.query_collected_fees());
+    // the receipt, not the reserve, carries the claim
+    state.deposit(ADDRESS1, RECEIVER_ADDRESS, 4);
     assert_eq!(
-        RustBigUint::from(4u32),
+        RustBigUint::ZERO,
         state.query_reserve_for_address(RECEIVER_ADDRESS)
     );
+    state
+        .world
+        .check_account(RECEIVER_ADDRESS)
+        .esdt_nft_balance_and_attributes(
+            receipt_token.clone(),
+            1,
+            1,
+            receipts::ReceiptAttributes::<StaticApi> {
+                amount: BigUint::from(3u32),
+                depositor: ADDRESS1.to_managed_address(),
+                timestamp: 0,
+            },
+        );
+
+    // the receiver sells the receipt to address2, who redeems it
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(ADDRESS2)
+        .single_esdt(&receipt_token, 1, &BigUint::from(1u32))
+        .raw_call("")
+        .run();
+
+    // redeeming credits a reserve, so the reserve cap and registration apply
+    state.set_deposit_limits(0, 0, 2, 0);
+    state.redeem_receipt_expect_error(ADDRESS2, &receipt_token, 1, "Receiver reserve cap exceeded");
+    state.set_deposit_limits(0, 0, 0, 0);
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_registration_required(true)
+        .run();
+    state.redeem_receipt_expect_error(ADDRESS2, &receipt_token, 1, "Receiver is not registered");
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .set_registration_required(false)
+        .run();
+
+    state.redeem_receipt(ADDRESS2, &receipt_token, 1);
     assert_eq!(
-        RustBigUint::from(1u32),
-        state.query_reserve_for_address(ADDRESS1)
+        RustBigUint::from(3u32),
+        state.query_reserve_for_address(ADDRESS2)
     );
-    assert_eq!(RustBigUint::ZERO, state.query_reserve_for_address(ADDRESS2));
-    state.check_account(OWNER_ADDRESS, 1);
-    state.check_account(ADDRESS1, 2);
-    state.check_account(ADDRESS2, 2);
-    state.check_account(RECEIVER_ADDRESS, 0);
+
+    state.withdraw(ADDRESS2);
+    state.check_account(ADDRESS2, 9);
 }
@@ -0,0 +1,285 @@
+use multiversx_sc_scenario::imports::*;
+
+use rust_challenge::*;
+
+const OWNER_ADDRESS: TestAddress = TestAddress::new("owner");
+const ADDRESS1: TestAddress = TestAddress::new("address1");
+const RECEIVER_ADDRESS: TestAddress = TestAddress::new("receiver");
+const CONTRACT_ADDRESS: TestSCAddress = TestSCAddress::new("rust-challenge");
+const LEGACY_CODE_PATH: MxscPath = MxscPath::new("output/rust_challenge_v0.mxsc.json");
+const CODE_PATH: MxscPath = MxscPath::new("output/rust_challenge.mxsc.json");
+
+/// The first released version of the contract, using storage layout version 0.
+mod legacy {
+    use multiversx_sc::imports::*;
+
+    #[multiversx_sc::contract]
+    pub trait RustChallengeV0 {
+        #[init]
+        fn init(&self, fee: BigUint) {
+            self.fee().set(fee);
+        }
+
+        #[payable("EGLD")]
+        #[endpoint]
+        fn deposit(&self, receiver: ManagedAddress) {
+            let payment_amount = self.call_value().egld_value().clone_value();
+            let fee = self.fee().get();
+            require!(payment_amount > fee, "Payments must be greater than fee");
+            self.collected_fees().update(|collected| *collected += &fee);
+            self.reserve_for_address(&receiver)
+                .update(|reserve| *reserve += payment_amount - fee);
+        }
+
+        #[upgrade]
+        fn upgrade(&self) {}
+
+        #[storage_mapper]
+        fn fee(&self) -> SingleValueMapper<BigUint>;
+
+        #[storage_mapper("collectedFees")]
+        fn collected_fees(&self) -> SingleValueMapper<BigUint>;
+
+        #[storage_mapper("reserveForAddress")]
+        fn reserve_for_address(&self, receiver: &ManagedAddress) -> SingleValueMapper<BigUint>;
+    }
+}
+
+fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+
+    blockchain.register_contract(LEGACY_CODE_PATH, legacy::ContractBuilder);
+    blockchain.register_contract(CODE_PATH, rust_challenge::ContractBuilder);
+
+    blockchain
+}
+
+struct UpgradeTest {
+    world: ScenarioWorld,
+}
+
+impl UpgradeTest {
+    fn new() -> Self {
+        let mut world = world();
+        world.account(OWNER_ADDRESS).balance(4).nonce(1);
+        world.account(ADDRESS1).balance(10).nonce(1);
+        world.account(RECEIVER_ADDRESS).nonce(1);
+
+        Self { world }
+    }
+
+    fn deploy_legacy(&mut self, fee: u32) {
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .raw_deploy()
+            .argument(&fee)
+            .code(LEGACY_CODE_PATH)
+            .new_address(CONTRACT_ADDRESS)
+            .run();
+    }
+
+    fn legacy_deposit(&mut self, receiver: TestAddress, value: u64) {
+        self.world
+            .tx()
+            .from(ADDRESS1)
+            .to(CONTRACT_ADDRESS)
+            .raw_call("deposit")
+            .argument(&receiver)
+            .egld(value)
+            .run();
+    }
+
+    /// Swaps in the current code through the `upgradeContract` builtin function and runs its
+    /// `upgrade` endpoint, the same way the protocol does.
+    fn upgrade(&mut self) {
+        let code = self
+            .world
+            .code_expression("mxsc:output/rust_challenge.mxsc.json")
+            .value;
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .raw_call("upgradeContract")
+            .argument(&code)
+            .argument(&CodeMetadata::UPGRADEABLE)
+            .run();
+    }
+
+    fn migrate_reserves(&mut self, addresses: Vec<TestAddress>) {
+        let addresses = addresses
+            .into_iter()
+            .map(|address| address.to_address())
+            .collect::<Vec<_>>();
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .migrate_reserves(MultiValueVec::from(addresses))
+            .run();
+    }
+
+    fn complete_migration(&mut self) {
+        self.world
+            .tx()
+            .from(OWNER_ADDRESS)
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .complete_migration()
+            .run();
+    }
+
+    fn query_storage_version(&mut self) -> u32 {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .storage_version()
+            .returns(ReturnsResult)
+            .run()
+    }
+
+    fn query_solvency(&mut self) -> rust_challenge_proxy::SolvencyInfo<StaticApi> {
+        self.world
+            .query()
+            .to(CONTRACT_ADDRESS)
+            .typed(rust_challenge_proxy::RustChallengeProxy)
+            .get_solvency()
+            .returns(ReturnsResult)
+            .run()
+    }
+}
+
+#[test]
+fn test_upgrade_from_v0() {
+    let mut state = UpgradeTest::new();
+    state.deploy_legacy(1);
+    state.legacy_deposit(RECEIVER_ADDRESS, 3);
+    state.legacy_deposit(RECEIVER_ADDRESS, 2);
+    state.legacy_deposit(OWNER_ADDRESS, 4);
+
+    state.upgrade();
+    assert_eq!(0, state.query_storage_version());
+
+    // reserves cannot move until they are indexed
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw()
+        .with_result(ExpectError(4, "Storage migration in progress"))
+        .run();
+
+    state.migrate_reserves(vec![RECEIVER_ADDRESS]);
+    // repeated and empty addresses are skipped
+    state.migrate_reserves(vec![RECEIVER_ADDRESS, OWNER_ADDRESS, ADDRESS1]);
+    state.complete_migration();
+    assert_eq!(1, state.query_storage_version());
+
+    let solvency = state.query_solvency();
+    assert_eq!(solvency.total_reserves, 6u32);
+    assert_eq!(solvency.collected_fees, 3u32);
+    assert_eq!(solvency.balance, 9u32);
+    assert!(solvency.is_solvent);
+
+    let fee: RustBigUint = state
+        .world
+        .query()
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .get_fee()
+        .returns(ReturnsResultUnmanaged)
+        .run();
+    assert_eq!(RustBigUint::from(1u32), fee);
+
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw()
+        .run();
+    state.world.check_account(RECEIVER_ADDRESS).balance(3);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw()
+        .run();
+    state.world.check_account(OWNER_ADDRESS).balance(10);
+    state.world.check_account(CONTRACT_ADDRESS).balance(0);
+}
+
+#[test]
+fn test_upgrade_with_missed_receiver() {
+    let mut state = UpgradeTest::new();
+    state.deploy_legacy(1);
+    state.legacy_deposit(RECEIVER_ADDRESS, 4);
+    state.legacy_deposit(OWNER_ADDRESS, 5);
+
+    state.upgrade();
+    state.migrate_reserves(vec![OWNER_ADDRESS]);
+    state.complete_migration();
+    assert_eq!(state.query_solvency().total_reserves, 4u32);
+
+    // the missed reserve is indexed when it is first touched
+    state
+        .world
+        .tx()
+        .from(RECEIVER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw()
+        .run();
+    state.world.check_account(RECEIVER_ADDRESS).balance(3);
+    let solvency = state.query_solvency();
+    assert_eq!(solvency.total_reserves, 4u32);
+    assert!(solvency.is_solvent);
+
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .withdraw()
+        .run();
+    state.world.check_account(OWNER_ADDRESS).balance(10);
+    state.world.check_account(CONTRACT_ADDRESS).balance(0);
+    assert_eq!(state.query_solvency().total_reserves, 0u32);
+}
+
+#[test]
+fn test_upgrade_current_layout() {
+    let mut state = UpgradeTest::new();
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .init(1u32)
+        .code(CODE_PATH)
+        .new_address(CONTRACT_ADDRESS)
+        .run();
+    assert_eq!(1, state.query_storage_version());
+
+    state.upgrade();
+    assert_eq!(1, state.query_storage_version());
+    state
+        .world
+        .tx()
+        .from(OWNER_ADDRESS)
+        .to(CONTRACT_ADDRESS)
+        .typed(rust_challenge_proxy::RustChallengeProxy)
+        .complete_migration()
+        .with_result(ExpectError(4, "No migration pending"))
+        .run();
+}
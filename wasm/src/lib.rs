@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                            6
-// Async Callback (empty):               1
-// Total number of exported functions:   9
+// Endpoints:                          123
+// Async Callback:                       1
+// Total number of exported functions: 126
 
 #![no_std]
 
@@ -21,12 +21,129 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         deposit => deposit
+        depositWithMemo => deposit_with_memo
+        transferReserve => transfer_reserve
         withdraw => withdraw
         setFee => set_fee
+        executeFeeProposal => execute_fee_proposal
         get_fee => get_fee
-        getReserveForAddress => get_reserve_for_address
+        setTransferFee => set_transfer_fee
+        getTransferFee => get_transfer_fee
         getCollectedFees => get_collected_fees
+        getSolvency => get_solvency
+        setDepositLimits => set_deposit_limits
+        getDepositLimits => get_deposit_limits
+        grantRole => grant_role
+        revokeRole => revoke_role
+        getRoleMembers => role_members
+        addToBlocklist => add_to_blocklist
+        removeFromBlocklist => remove_from_blocklist
+        addToAllowlist => add_to_allowlist
+        removeFromAllowlist => remove_from_allowlist
+        setAllowlistOnly => set_allowlist_only
+        isAddressPermitted => is_address_permitted
+        getBlocklist => blocklist
+        getAllowlist => allowlist
+        isAllowlistOnly => allowlist_only
+        setRegistrationRequired => set_registration_required
+        register => register
+        unregister => unregister
+        getReceiverInfo => get_receiver_info
+        isRegistrationRequired => registration_required
+        isMigrationPending => migration_pending
+        getLastActivity => last_activity
+        getReserveForAddress => get_reserve_for_address
+        getTotalReserves => total_reserves
+        getDepositHistory => get_deposit_history
+        getReceiversCount => get_receivers_count
+        getReceivers => get_receivers
+        setBeneficiaries => set_beneficiaries
+        claimFees => claim_fees
+        getBeneficiaries => get_beneficiaries
+        getAccruedFees => accrued_fees
+        getTotalAccruedFees => total_accrued_fees
+        setReferralShare => set_referral_share
+        getReferralShare => referral_share_bps
+        getReferralEarnings => referral_earnings
+        getReferredVolume => referred_volume
+        withdrawAndCall => withdraw_and_call
+        pushPayouts => push_payouts
+        setDormancyConfig => set_dormancy_config
+        sweepDormantReserve => sweep_dormant_reserve
+        getTimeToExpiry => get_time_to_expiry
+        getDormancyPeriod => dormancy_period
+        getDormancyTreasury => dormancy_treasury
+        migrateReserves => migrate_reserves
+        completeMigration => complete_migration
+        getStorageVersion => storage_version
+        setWithdrawalRateLimit => set_withdrawal_rate_limit
+        getRemainingAllowance => get_remaining_allowance
+        getMaxWithdrawalPerWindow => max_withdrawal_per_window
+        getWithdrawalWindowLength => withdrawal_window_length
+        setCircuitBreaker => set_circuit_breaker
+        resetCircuitBreaker => reset_circuit_breaker
+        getOutflowThreshold => outflow_threshold
+        getOutflowWindowLength => outflow_window_length
+        getOutflowWindow => outflow_window
+        areWithdrawalsPaused => withdrawals_paused
+        nominateRecoveryAddress => nominate_recovery_address
+        cancelRecoveryNomination => cancel_recovery_nomination
+        claimAsRecovery => claim_as_recovery
+        getRecoveryNomination => get_recovery_nomination
+        approveOperator => approve_operator
+        revokeOperator => revoke_operator
+        approveDestination => approve_destination
+        revokeDestination => revoke_destination
+        withdrawFor => withdraw_for
+        getOperatorApproval => get_operator_approval
+        getOperators => get_operators
+        getApprovedDestinations => approved_destinations
+        withdrawWithSignature => withdraw_with_signature
+        getWithdrawalAuthorizationMessage => get_withdrawal_authorization_message
+        getWithdrawalNonce => withdrawal_nonce
+        createEscrow => create_escrow
+        releaseTranche => release_tranche
+        cancelTranche => cancel_tranche
+        getEscrow => get_escrow
+        getLastEscrowId => last_escrow_id
+        getTotalEscrowed => total_escrowed
+        createSubscription => create_subscription
+        topUpSubscription => top_up_subscription
+        pullPayment => pull_payment
+        cancelSubscription => cancel_subscription
+        getSubscription => get_subscription
+        getAvailableToPull => get_available_to_pull
+        getLastSubscriptionId => last_subscription_id
+        getTotalSubscriptionBalances => total_subscription_balances
+        setMembershipDiscount => set_membership_discount
+        lockMembershipToken => lock_membership_token
+        unlockMembershipToken => unlock_membership_token
+        getMembershipToken => get_membership_token
+        getMembershipDiscount => membership_discount
+        getLockedMembershipToken => get_locked_membership_token
+        setGovernanceConfig => set_governance_config
+        proposeFee => propose_fee
+        vote => vote
+        getVotingWeight => get_voting_weight
+        getProposal => get_proposal
+        getLastProposalId => last_proposal_id
+        hasVoted => has_voted
+        getVotingPeriod => voting_period
+        getQuorum => quorum_bps
+        getDepositedVolume => deposited_volume
+        getTotalDepositedVolume => total_deposited_volume
+        setFeeTreasury => set_fee_treasury
+        getFeeTreasury => fee_treasury
+        getFeeForwardThreshold => fee_forward_threshold
+        getAddressStats => get_address_stats
+        getGlobalStats => get_global_stats
+        issueReceiptToken => issue_receipt_token
+        setReceiptMode => set_receipt_mode
+        redeemReceipt => redeem_receipt
+        getReceiptToken => receipt_token
+        isReceiptModeEnabled => receipt_mode
+        getOutstandingReceipts => outstanding_receipts
     )
 }
 
-multiversx_sc_wasm_adapter::async_callback_empty! {}
+multiversx_sc_wasm_adapter::async_callback! { rust_challenge }